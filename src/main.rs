@@ -1,10 +1,15 @@
+// Набор виджетов шире, чем использует демонстрация: неиспользуемое здесь — его публичный интерфейс.
+#[allow(dead_code)]
 mod widgets;
 
 use widgets::{Label, Button, Window, Widget};
 
 fn main() {
-    let mut window = Window::new("Демонстрация графического интерфейса Rust 1.23");
-    window.add_widget(Box::new(Label::new("Это маленькая демонстрация графического интерфейса.")));
-    window.add_widget(Box::new(Button::new("Нажми меня!")));
+    let window = ui! {
+        Window { title: "Демонстрация графического интерфейса Rust 1.23";
+            Label { text: "Это маленькая демонстрация графического интерфейса." },
+            Button { label: "Нажми меня!" },
+        }
+    };
     window.draw();
-}
\ No newline at end of file
+}
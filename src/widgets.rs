@@ -1,16 +1,206 @@
+pub mod access;
+pub mod app;
+pub mod bar_chart;
+pub mod border;
+pub mod boxes;
 pub mod button;
+pub mod canvas;
+pub mod chart;
+pub mod checkbox;
+pub mod dialog;
+pub mod document;
+pub mod edit_buffer;
+pub mod event;
+pub mod export;
+pub mod gauge;
+pub mod json;
 pub mod label;
+pub mod layout;
+pub mod line_plot;
+pub mod list_view;
+pub mod menu;
+pub mod progress_bar;
+pub mod radio_group;
+pub mod screen;
+#[cfg(test)]
+pub mod snapshot;
+pub mod sparkline;
+pub mod spinner;
+pub mod split_pane;
+pub mod style;
+pub mod table;
+pub mod tabs;
+pub mod terminal;
+pub mod text_area;
+pub mod text_field;
+pub mod theme;
+pub mod toggle;
+pub mod toml;
+pub mod ui;
+pub mod unicode;
 pub mod window;
 
-pub use button::Button;
-pub use label::Label;
-pub use window::Window;
+// Публичный интерфейс набора; демонстрация в main.rs берёт из него немногое.
+#[allow(unused_imports)]
+pub use self::{
+    access::{find_by_id, find_by_name, transcript, AccessRole, Accessible, WithId},
+    app::App,
+    bar_chart::BarChart,
+    border::BorderStyle,
+    boxes::{BoxBuilder, CrossAlign, HBox, VBox},
+    button::{Button, ButtonBuilder},
+    canvas::{Canvas, Cell},
+    chart::Scale,
+    checkbox::Checkbox,
+    dialog::{Choice, Dialog},
+    document::{load_json, load_toml, to_json, to_toml, LoadError, Position, SaveError},
+    event::{Event, KeyCode, KeyEvent, Modifiers, MouseButton, MouseEvent, MouseKind},
+    export::{export, AnsiRenderer, HtmlRenderer, PlainRenderer, Renderer, SvgRenderer},
+    gauge::Gauge,
+    label::{Label, LabelBuilder},
+    layout::{Align, Constraints, Rect, Size, Wrap},
+    line_plot::LinePlot,
+    list_view::{FnModel, ListModel, ListView},
+    menu::{Commands, ContextMenu, Menu, MenuBar, MenuItem},
+    progress_bar::ProgressBar,
+    radio_group::RadioGroup,
+    screen::Screen,
+    sparkline::Sparkline,
+    spinner::Spinner,
+    split_pane::{Orientation, SplitPane},
+    style::{Color, Style},
+    table::{ColumnWidth, Table},
+    tabs::Tabs,
+    terminal::{cached_terminal_size, terminal_size, LiveRegion, Terminal},
+    text_area::TextArea,
+    text_field::TextField,
+    theme::{Role, Theme},
+    toggle::Toggle,
+    window::{Window, WindowBuilder},
+};
+
 pub trait Widget {
     fn width(&self) -> usize;
-    fn draw_into(&self, buffer: &mut dyn std::fmt::Write);
+    fn height(&self) -> usize;
+
+    /// Желаемый размер виджета без внешних ограничений.
+    fn size_hint(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+
+    /// Проход измерения: размер, который виджет займёт в рамках ограничений родителя.
+    fn measure(&self, constraints: Constraints) -> Size {
+        constraints.constrain(self.size_hint())
+    }
+
+    /// Проход размещения: рисует виджет на холсте внутри области `area`.
+    fn render(&self, canvas: &mut Canvas, area: Rect);
+
+    /// Выводит ровно `size.height` строк по `size.width` столбцов.
+    fn draw_sized(&self, size: Size, buffer: &mut dyn std::fmt::Write) {
+        let mut canvas = Canvas::new(size);
+        self.render(&mut canvas, Rect::from_size(size));
+        write!(buffer, "{}", canvas).unwrap();
+    }
+
+    /// Может ли виджет получать фокус клавиатуры.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn set_focused(&mut self, _focused: bool) {}
+
+    /// Обрабатывает событие; `true` — событие поглощено и виджет надо перерисовать.
+    /// Координаты событий мыши отсчитываются от левого верхнего угла виджета.
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    /// Клавиша-ускоритель (Alt с буквой, функциональные клавиши), которую виджет
+    /// ловит, даже не будучи в фокусе. `App` предлагает её всем виджетам до обычной обработки.
+    fn handle_shortcut(&mut self, _key: &KeyEvent) -> bool {
+        false
+    }
+
+    /// Попадает ли фокусируемый виджет в обход по Tab. Остальные фокусируются
+    /// только мышью или ускорителем, а мышь получают и не будучи фокусируемыми.
+    fn is_tab_stop(&self) -> bool {
+        true
+    }
+
+    /// Дочерние виджеты контейнера в порядке обхода фокусом.
+    fn children(&self) -> Vec<&dyn Widget> {
+        Vec::new()
+    }
+
+    /// Описание виджета для сохранения в TOML или JSON (см. `document`);
+    /// `None` — виджет не умеет себя описать.
+    fn describe(&self) -> Option<document::Value> {
+        None
+    }
+
+    /// Применяет тему к виджету и его потомкам; то, что настроено у виджета явно, не меняется.
+    fn apply_theme(&mut self, theme: &theme::Theme) {
+        for child in self.children_mut() {
+            child.apply_theme(theme);
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        Vec::new()
+    }
+
+    /// Виджет изменился не через `handle_event` (таймер, новые данные) и просит перерисовки.
+    /// Контейнер грязный, если грязен кто-то из потомков.
+    fn is_dirty(&self) -> bool {
+        self.children().into_iter().any(|child| child.is_dirty())
+    }
+
+    /// Снимает отметку после вывода кадра.
+    fn mark_clean(&mut self) {
+        for child in self.children_mut() {
+            child.mark_clean();
+        }
+    }
+
+    /// Области дочерних виджетов при отрисовке в `area`, в том же порядке, что `children`.
+    fn child_areas(&self, _area: Rect) -> Vec<Rect> {
+        Vec::new()
+    }
+
+    /// Идентификатор для поиска в дереве (`access::find_by_id`); задаётся через `with_id`.
+    fn id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Роль, имя и значение виджета для программ чтения с экрана и поиска в тестах.
+    fn accessible(&self) -> access::Accessible {
+        access::Accessible::new(access::AccessRole::Group, "")
+    }
+
+    /// Оборачивает виджет, давая ему идентификатор; дерево от этого не меняется.
+    fn with_id(self, id: &str) -> access::WithId<Self>
+    where
+        Self: Sized,
+    {
+        access::WithId::new(id, self)
+    }
+
+    fn draw_into(&self, buffer: &mut dyn std::fmt::Write) {
+        let size = self.measure(Constraints::unbounded());
+        self.draw_sized(size, buffer);
+    }
+
+    /// Печатает виджет не шире терминала, если его размер известен.
     fn draw(&self) {
         let mut buffer = String::new();
-        self.draw_into(&mut buffer);
+        match terminal::cached_terminal_size() {
+            Some(screen) => {
+                let size = self.measure(Constraints { max_width: screen.width, ..Constraints::unbounded() });
+                self.draw_sized(size, &mut buffer);
+            }
+            None => self.draw_into(&mut buffer),
+        }
         println!("{}", buffer);
     }
 }
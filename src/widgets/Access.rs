@@ -0,0 +1,297 @@
+use crate::widgets::app::WidgetPath;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::Value;
+use crate::widgets::event::{Event, KeyEvent};
+use crate::widgets::layout::{Constraints, Rect, Size};
+use crate::widgets::theme::Theme;
+use crate::widgets::Widget;
+
+/// Чем виджет является для программ чтения с экрана.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessRole {
+    /// Контейнер без собственного смысла, например `HBox`.
+    Group,
+    Window,
+    Label,
+    Button,
+    CheckBox,
+    Switch,
+    RadioGroup,
+    TextBox,
+    List,
+    Table,
+    ProgressBar,
+    MenuBar,
+    Menu,
+    TabList,
+    Splitter,
+    Chart,
+    /// Сообщение о ходе работы без известного объёма.
+    Status,
+}
+
+impl AccessRole {
+    /// Название роли так, как его произносит транскрипт.
+    pub fn name(self) -> &'static str {
+        match self {
+            AccessRole::Group => "группа",
+            AccessRole::Window => "окно",
+            AccessRole::Label => "надпись",
+            AccessRole::Button => "кнопка",
+            AccessRole::CheckBox => "флажок",
+            AccessRole::Switch => "переключатель",
+            AccessRole::RadioGroup => "группа переключателей",
+            AccessRole::TextBox => "поле ввода",
+            AccessRole::List => "список",
+            AccessRole::Table => "таблица",
+            AccessRole::ProgressBar => "индикатор выполнения",
+            AccessRole::MenuBar => "строка меню",
+            AccessRole::Menu => "меню",
+            AccessRole::TabList => "вкладки",
+            AccessRole::Splitter => "разделитель",
+            AccessRole::Chart => "диаграмма",
+            AccessRole::Status => "состояние",
+        }
+    }
+}
+
+/// Доступное описание виджета: роль, имя (надпись, заголовок) и текущее значение.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accessible {
+    pub role: AccessRole,
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl Accessible {
+    pub fn new(role: AccessRole, name: &str) -> Accessible {
+        Accessible { role, name: name.to_owned(), value: None }
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Accessible {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Одна строка транскрипта: `кнопка «OK»`, `флажок «Звук», отмечен`.
+    /// Надпись читается просто своим текстом.
+    fn phrase(&self) -> String {
+        let mut phrase = match (self.role, self.name.is_empty()) {
+            (AccessRole::Label, _) => self.name.replace('\n', " "),
+            (role, true) => role.name().to_owned(),
+            (role, false) => format!("{} «{}»", role.name(), self.name),
+        };
+        if let Some(value) = &self.value {
+            phrase.push_str(", ");
+            phrase.push_str(value);
+        }
+        phrase
+    }
+}
+
+/// Виджет с идентификатором. Во всём остальном ведёт себя как обёрнутый виджет
+/// и не добавляет уровня в дерево, так что пути к потомкам не меняются.
+pub struct WithId<W: Widget> {
+    id: String,
+    widget: W,
+}
+
+impl<W: Widget> WithId<W> {
+    pub fn new(id: &str, widget: W) -> WithId<W> {
+        WithId { id: id.to_owned(), widget }
+    }
+
+    pub fn inner(&self) -> &W {
+        &self.widget
+    }
+
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.widget
+    }
+}
+
+impl<W: Widget> Widget for WithId<W> {
+    fn width(&self) -> usize {
+        self.widget.width()
+    }
+
+    fn height(&self) -> usize {
+        self.widget.height()
+    }
+
+    fn size_hint(&self) -> Size {
+        self.widget.size_hint()
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.widget.measure(constraints)
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        self.widget.render(canvas, area);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.widget.is_focusable()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.widget.set_focused(focused);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.widget.handle_event(event)
+    }
+
+    fn handle_shortcut(&mut self, key: &KeyEvent) -> bool {
+        self.widget.handle_shortcut(key)
+    }
+
+    fn is_tab_stop(&self) -> bool {
+        self.widget.is_tab_stop()
+    }
+
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.widget.children()
+    }
+
+    fn describe(&self) -> Option<Value> {
+        self.widget.describe()
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.widget.apply_theme(theme);
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.widget.children_mut()
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.widget.is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.widget.mark_clean();
+    }
+
+    fn child_areas(&self, area: Rect) -> Vec<Rect> {
+        self.widget.child_areas(area)
+    }
+
+    fn id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+
+    fn accessible(&self) -> Accessible {
+        self.widget.accessible()
+    }
+}
+
+/// Все виджеты дерева с путями к ним, в порядке обхода в глубину — том же, что у фокуса.
+pub fn walk(root: &dyn Widget) -> Vec<(WidgetPath, &dyn Widget)> {
+    fn visit<'a>(widget: &'a dyn Widget, path: &mut WidgetPath, nodes: &mut Vec<(WidgetPath, &'a dyn Widget)>) {
+        nodes.push((path.clone(), widget));
+        for (i, child) in widget.children().into_iter().enumerate() {
+            path.push(i);
+            visit(child, path, nodes);
+            path.pop();
+        }
+    }
+    let mut nodes = Vec::new();
+    visit(root, &mut Vec::new(), &mut nodes);
+    nodes
+}
+
+/// Путь к первому виджету, подходящему под условие.
+pub fn find(root: &dyn Widget, predicate: impl Fn(&dyn Widget) -> bool) -> Option<WidgetPath> {
+    walk(root).into_iter().find(|(_, widget)| predicate(*widget)).map(|(path, _)| path)
+}
+
+pub fn find_by_id(root: &dyn Widget, id: &str) -> Option<WidgetPath> {
+    find(root, |widget| widget.id() == Some(id))
+}
+
+/// Виджет с ролью и доступным именем: «кнопка OK», «флажок Запомнить меня».
+pub fn find_by_name(root: &dyn Widget, role: AccessRole, name: &str) -> Option<WidgetPath> {
+    find(root, |widget| {
+        let accessible = widget.accessible();
+        accessible.role == role && accessible.name == name
+    })
+}
+
+/// Линейный текст интерфейса для программ чтения с экрана: по строке на виджет
+/// в порядке обхода фокусом. Безымянные группы пропускаются, виджет в фокусе отмечен.
+pub fn transcript(root: &dyn Widget, focus: Option<&[usize]>) -> String {
+    let mut out = String::new();
+    for (path, widget) in walk(root) {
+        let accessible = widget.accessible();
+        if accessible.role == AccessRole::Group && accessible.name.is_empty() && accessible.value.is_none() {
+            continue;
+        }
+        out.push_str(&accessible.phrase());
+        if focus == Some(path.as_slice()) {
+            out.push_str(", в фокусе");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::app::{widget_at, App};
+    use crate::widgets::event::KeyCode;
+    use crate::widgets::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn form(saved: &Rc<RefCell<bool>>) -> Window {
+        let mut window = Window::new("Настройки");
+        window.add_widget(Box::new(Label::new("Имя пользователя:")));
+        let mut name = TextField::new(20);
+        name.set_text("Ксения");
+        window.add_widget(Box::new(name.with_id("name")));
+        let mut remember = Checkbox::new("Запомнить меня");
+        remember.set_checked(true);
+        window.add_widget(Box::new(remember));
+        let mut buttons = HBox::new();
+        let mut save = Button::new("Сохранить");
+        let saved = Rc::clone(saved);
+        save.set_on_click(move || *saved.borrow_mut() = true);
+        buttons.add_widget(Box::new(save.with_id("save")));
+        buttons.add_widget(Box::new(Button::new("Отмена")));
+        window.add_widget(Box::new(buttons));
+        window
+    }
+
+    #[test]
+    fn transcript_in_focus_order() {
+        let app = App::new(Box::new(form(&Rc::default())));
+        assert_eq!(
+            app.transcript(),
+            "окно «Настройки»\n\
+             Имя пользователя:\n\
+             поле ввода, Ксения, в фокусе\n\
+             флажок «Запомнить меня», отмечен\n\
+             кнопка «Сохранить»\n\
+             кнопка «Отмена»\n"
+        );
+    }
+
+    #[test]
+    fn query_by_id_and_name() {
+        let saved = Rc::new(RefCell::new(false));
+        let mut app = App::new(Box::new(form(&saved)));
+        assert_eq!(find_by_id(app.root(), "name"), Some(vec![1]));
+        assert_eq!(find_by_id(app.root(), "save"), find_by_name(app.root(), AccessRole::Button, "Сохранить"));
+        let path = find_by_name(app.root(), AccessRole::Button, "Сохранить").unwrap();
+        assert_eq!(widget_at(app.root(), &path).and_then(|widget| widget.id()), Some("save"));
+
+        app.set_focus(Some(path));
+        app.handle_event(&Event::key(KeyCode::Enter));
+        assert!(*saved.borrow());
+        assert_eq!(find_by_name(app.root(), AccessRole::Button, "Удалить"), None);
+    }
+}
@@ -0,0 +1,565 @@
+use std::io;
+use crate::widgets::access::{transcript, walk};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::dialog::Dialog;
+use crate::widgets::event::{Event, KeyCode, KeyEvent, MouseEvent, MouseKind};
+use crate::widgets::layout::{Constraints, Rect, Size};
+use crate::widgets::screen::Screen;
+use crate::widgets::Widget;
+
+/// Путь к виджету в дереве: индексы в `children()` от корня.
+pub type WidgetPath = Vec<usize>;
+
+/// Виджет по пути от `root`.
+pub fn widget_at<'a>(root: &'a dyn Widget, path: &[usize]) -> Option<&'a dyn Widget> {
+    match path.split_first() {
+        None => Some(root),
+        Some((&index, rest)) => widget_at(root.children().into_iter().nth(index)?, rest),
+    }
+}
+
+pub fn widget_at_mut<'a>(root: &'a mut dyn Widget, path: &[usize]) -> Option<&'a mut dyn Widget> {
+    match path.split_first() {
+        None => Some(root),
+        Some((&index, rest)) => widget_at_mut(root.children_mut().into_iter().nth(index)?, rest),
+    }
+}
+
+/// Область виджета по пути, если корень нарисован в `area`.
+pub fn area_of(root: &dyn Widget, area: Rect, path: &[usize]) -> Option<Rect> {
+    match path.split_first() {
+        None => Some(area),
+        Some((&index, rest)) => {
+            let child = root.children().into_iter().nth(index)?;
+            area_of(child, root.child_areas(area).into_iter().nth(index)?, rest)
+        }
+    }
+}
+
+/// Все виджеты, до которых доходит Tab, в порядке обхода в глубину.
+pub fn focus_chain(root: &dyn Widget) -> Vec<WidgetPath> {
+    fn walk(widget: &dyn Widget, path: &mut WidgetPath, chain: &mut Vec<WidgetPath>) {
+        if widget.is_focusable() && widget.is_tab_stop() {
+            chain.push(path.clone());
+        }
+        for (i, child) in widget.children().into_iter().enumerate() {
+            path.push(i);
+            walk(child, path, chain);
+            path.pop();
+        }
+    }
+    let mut chain = Vec::new();
+    walk(root, &mut Vec::new(), &mut chain);
+    chain
+}
+
+/// Самый глубокий виджет под точкой `x, y` и его область.
+pub fn hit_test(root: &dyn Widget, area: Rect, x: usize, y: usize) -> Option<(WidgetPath, Rect)> {
+    if !area.contains(x, y) {
+        return None;
+    }
+    let mut path = Vec::new();
+    let mut widget = root;
+    let mut area = area;
+    'descend: loop {
+        for (i, (child, child_area)) in widget.children().into_iter().zip(widget.child_areas(area)).enumerate() {
+            if child_area.contains(x, y) {
+                path.push(i);
+                widget = child;
+                area = child_area;
+                continue 'descend;
+            }
+        }
+        return Some((path, area));
+    }
+}
+
+/// Диалог поверх экрана и фокус, который был под ним до его открытия.
+struct Layer {
+    dialog: Dialog,
+    focus: Option<WidgetPath>,
+}
+
+/// Цикл событий: держит корневой виджет, фокус и перерисовывает экран после изменений.
+/// Источник событий любой — терминал или заранее записанный сценарий.
+/// Открытые диалоги лежат стопкой над корнем; события и фокус достаются верхнему.
+pub struct App {
+    root: Box<dyn Widget>,
+    layers: Vec<Layer>,
+    /// Путь от корня активного слоя: верхнего диалога или, если их нет, корневого виджета.
+    focus: Option<WidgetPath>,
+    /// Куда вернуть фокус, когда его отпустит виджет вне обхода по Tab, например строка меню.
+    return_focus: Option<WidgetPath>,
+    /// Виджет, на котором нажата кнопка мыши: перетаскивание и отпускание достаются ему.
+    grab: Option<(WidgetPath, Rect)>,
+    screen: Screen,
+    /// Размер экрана; `None` — экран подстраивается под корневой виджет.
+    viewport: Option<Size>,
+    /// Перерисовка запрошена программой через `request_redraw`.
+    dirty: bool,
+}
+
+impl App {
+    pub fn new(root: Box<dyn Widget>) -> App {
+        let mut app = App { root, layers: Vec::new(), focus: None, return_focus: None, grab: None, screen: Screen::new(), viewport: None, dirty: false };
+        app.focus_next();
+        app
+    }
+
+    pub fn root(&self) -> &dyn Widget {
+        self.root.as_ref()
+    }
+
+    pub fn root_mut(&mut self) -> &mut dyn Widget {
+        self.root.as_mut()
+    }
+
+    /// Задаёт размер экрана. Корень раскладывается заново под новую ширину,
+    /// а то, что в экран не помещается, обрезается.
+    pub fn set_viewport(&mut self, viewport: Option<Size>) {
+        self.viewport = viewport;
+    }
+
+    /// Область экрана: размер терминала или, если он не задан, размер корня.
+    pub fn screen_area(&self) -> Rect {
+        match self.viewport {
+            Some(viewport) => Rect::from_size(viewport),
+            None => self.area(),
+        }
+    }
+
+    /// Область, в которой рисуется корневой виджет.
+    pub fn area(&self) -> Rect {
+        match self.viewport {
+            Some(viewport) => Rect::from_size(self.root.measure(Constraints::loose(viewport))),
+            None => Rect::from_size(self.root.measure(Constraints::unbounded())),
+        }
+    }
+
+    /// Виджет, которому сейчас достаются события: верхний диалог или корень.
+    fn active(&self) -> &dyn Widget {
+        match self.layers.last() {
+            Some(layer) => &layer.dialog,
+            None => self.root.as_ref(),
+        }
+    }
+
+    fn active_mut(&mut self) -> &mut dyn Widget {
+        match self.layers.last_mut() {
+            Some(layer) => &mut layer.dialog,
+            None => self.root.as_mut(),
+        }
+    }
+
+    fn active_area(&self) -> Rect {
+        match self.layers.last() {
+            Some(layer) => layer.dialog.area_over(self.screen_area()),
+            None => self.area(),
+        }
+    }
+
+    /// Открывает модальный диалог: фокус переходит в него, пока он не закроется.
+    pub fn show_dialog(&mut self, dialog: Dialog) {
+        let focus = self.focus.clone();
+        self.set_focus(None);
+        self.layers.push(Layer { dialog, focus });
+        self.focus_next();
+    }
+
+    pub fn dialog(&self) -> Option<&Dialog> {
+        self.layers.last().map(|layer| &layer.dialog)
+    }
+
+    /// Убирает верхний диалог, если в нём сделан выбор, и возвращает фокус на место.
+    fn close_dialog(&mut self) -> bool {
+        if self.layers.last().is_none_or(|layer| layer.dialog.choice().is_none()) {
+            return false;
+        }
+        let Some(mut layer) = self.layers.pop() else {
+            return false;
+        };
+        self.focus = None;
+        layer.dialog.close();
+        self.set_focus(layer.focus);
+        true
+    }
+
+    /// Текст для программ чтения с экрана: активный слой в порядке фокуса (см. `access::transcript`).
+    pub fn transcript(&self) -> String {
+        transcript(self.active(), self.focused())
+    }
+
+    pub fn focused(&self) -> Option<&[usize]> {
+        self.focus.as_deref()
+    }
+
+    pub fn set_focus(&mut self, path: Option<WidgetPath>) {
+        let leaves_tab_order = path.as_ref().is_some_and(|path| {
+            widget_at(self.active(), path).is_some_and(|widget| !widget.is_tab_stop())
+        });
+        self.grab = None;
+        if !leaves_tab_order {
+            self.return_focus = None;
+        } else if self.return_focus.is_none() {
+            self.return_focus = self.focus.clone();
+        }
+        if let Some(old) = self.focus.take() {
+            if let Some(widget) = widget_at_mut(self.active_mut(), &old) {
+                widget.set_focused(false);
+            }
+        }
+        if let Some(new) = &path {
+            if let Some(widget) = widget_at_mut(self.active_mut(), new) {
+                widget.set_focused(true);
+            }
+        }
+        self.focus = path;
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let chain = focus_chain(self.active());
+        if chain.is_empty() {
+            return;
+        }
+        let current = self.focus.as_ref().and_then(|focus| chain.iter().position(|path| path == focus));
+        let next = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => chain.len() - 1,
+            (Some(i), true) => (i + 1) % chain.len(),
+            (Some(i), false) => (i + chain.len() - 1) % chain.len(),
+        };
+        self.set_focus(Some(chain[next].clone()));
+    }
+
+    pub fn focus_next(&mut self) {
+        self.move_focus(true);
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.move_focus(false);
+    }
+
+    /// Фокусируемый виджет под точкой (или ближайший такой предок) и его область.
+    fn mouse_target(&self, x: usize, y: usize) -> Option<(WidgetPath, Rect)> {
+        let (mut path, _) = hit_test(self.active(), self.active_area(), x, y)?;
+        loop {
+            if widget_at(self.active(), &path).is_some_and(|widget| widget.is_focusable() || !widget.is_tab_stop()) {
+                return Some((path.clone(), area_of(self.active(), self.active_area(), &path)?));
+            }
+            path.pop()?;
+        }
+    }
+
+    /// Виджет вне обхода по Tab, переставший принимать фокус, возвращает его туда, откуда забрал.
+    fn release_focus(&mut self) {
+        let Some(focus) = &self.focus else {
+            return;
+        };
+        if widget_at(self.active(), focus).is_some_and(|widget| !widget.is_tab_stop() && !widget.is_focusable()) {
+            let previous = self.return_focus.take();
+            self.set_focus(previous);
+        }
+    }
+
+    /// Предлагает клавишу-ускоритель всем виджетам активного слоя по порядку;
+    /// поймавший её виджет получает фокус.
+    fn dispatch_shortcut(&mut self, key: &KeyEvent) -> bool {
+        let paths: Vec<WidgetPath> = walk(self.active()).into_iter().map(|(path, _)| path).collect();
+        for path in paths {
+            if widget_at_mut(self.active_mut(), &path).is_some_and(|widget| widget.handle_shortcut(key)) {
+                let focusable = widget_at(self.active(), &path).is_some_and(|widget| widget.is_focusable());
+                if focusable && self.focus.as_ref() != Some(&path) {
+                    self.set_focus(Some(path));
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Tab и Shift-Tab переключают фокус, Alt с буквой и F-клавиши сначала проходят как ускорители,
+    /// нажатие мыши фокусирует виджет под курсором,
+    /// `Event::Resize` меняет размер экрана, остальные события получает виджет в фокусе,
+    /// а непоглощённые клавиши — открытый диалог.
+    /// Мимо открытого диалога события не проходят. Возвращает `true`, если нужна перерисовка.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        let changed = self.dispatch(event);
+        self.release_focus();
+        self.close_dialog() || changed
+    }
+
+    fn dispatch(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Key(key) if key.is(KeyCode::Tab) && !key.modifiers.shift => {
+                self.focus_next();
+                return true;
+            }
+            Event::Key(key) if key.code == KeyCode::BackTab || (key.code == KeyCode::Tab && key.modifiers.shift) => {
+                self.focus_prev();
+                return true;
+            }
+            Event::Mouse(mouse) => {
+                // Событие мыши получает ближайший к точке фокусируемый предок; нажатие его фокусирует,
+                // а перетаскивание и отпускание идут туда, где кнопку нажали.
+                let grabbed = match mouse.kind {
+                    MouseKind::Drag(_) | MouseKind::Release => self.grab.clone(),
+                    _ => None,
+                };
+                let Some((target, area)) = grabbed.or_else(|| self.mouse_target(mouse.x, mouse.y)) else {
+                    return false;
+                };
+                match mouse.kind {
+                    MouseKind::Press(_) => {
+                        if self.focus.as_ref() != Some(&target) {
+                            self.set_focus(Some(target.clone()));
+                        }
+                        self.grab = Some((target.clone(), area));
+                    }
+                    MouseKind::Release => self.grab = None,
+                    _ => {}
+                }
+                let local = Event::Mouse(MouseEvent {
+                    x: mouse.x.saturating_sub(area.x),
+                    y: mouse.y.saturating_sub(area.y),
+                    ..*mouse
+                });
+                return widget_at_mut(self.active_mut(), &target).is_some_and(|widget| widget.handle_event(&local));
+            }
+            Event::Resize(size) => {
+                self.set_viewport(Some(*size));
+                return true;
+            }
+            Event::Key(key) if (key.modifiers.alt || matches!(key.code, KeyCode::F(_))) && self.dispatch_shortcut(key) => {
+                return true;
+            }
+            _ => {}
+        }
+        if let Some(focus) = self.focus.clone() {
+            if widget_at_mut(self.active_mut(), &focus).is_some_and(|widget| widget.handle_event(event)) {
+                return true;
+            }
+        }
+        match self.layers.last_mut() {
+            Some(layer) => layer.dialog.handle_event(event),
+            None => false,
+        }
+    }
+
+    /// Текущий кадр целиком: корень, а над ним диалоги в порядке открытия.
+    pub fn frame(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.screen_area().size());
+        self.root.render(&mut canvas, self.area());
+        for layer in &self.layers {
+            layer.dialog.present(&mut canvas, self.screen_area());
+        }
+        canvas
+    }
+
+    /// Просит перерисовать экран, например после того, как программа поменяла виджеты через `root_mut`.
+    pub fn request_redraw(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Нужна ли перерисовка: её запросили или какой-то виджет помечен как изменившийся.
+    pub fn needs_redraw(&self) -> bool {
+        self.dirty || self.root.is_dirty() || self.layers.iter().any(|layer| layer.dialog.is_dirty())
+    }
+
+    /// Выводит кадр разницей с предыдущим и снимает отметки о перерисовке.
+    pub fn present(&mut self, out: &mut dyn io::Write) -> io::Result<()> {
+        let frame = self.frame();
+        self.screen.present(frame, out)?;
+        self.root.mark_clean();
+        for layer in &mut self.layers {
+            layer.dialog.mark_clean();
+        }
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Рисует кадр, затем обрабатывает события до конца потока или Ctrl-C / Ctrl-Q,
+    /// перерисовывая после каждого изменения только то, что изменилось.
+    pub fn run(&mut self, events: impl IntoIterator<Item = Event>, out: &mut dyn io::Write) -> io::Result<()> {
+        self.present(out)?;
+        for event in events {
+            if let Event::Key(key) = event {
+                if key.modifiers.ctrl && matches!(key.code, KeyCode::Char('c') | KeyCode::Char('q')) {
+                    break;
+                }
+            }
+            if self.handle_event(&event) || self.needs_redraw() {
+                self.present(out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::event::KeyEvent;
+    use crate::widgets::{Button, Choice, HBox, Label, PlainRenderer, Renderer, Window};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn form(log: &Rc<RefCell<Vec<&'static str>>>) -> App {
+        let mut window = Window::new("Форма");
+        window.add_widget(Box::new(Label::new("Сохранить изменения?")));
+        let mut row = HBox::new();
+        for name in ["Да", "Нет"] {
+            let mut button = Button::new(name);
+            let log = Rc::clone(log);
+            button.set_on_click(move || log.borrow_mut().push(name));
+            row.add_widget(Box::new(button));
+        }
+        window.add_widget(Box::new(row));
+        App::new(Box::new(window))
+    }
+
+    #[test]
+    fn tab_cycles_focus() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut app = form(&log);
+        assert_eq!(focus_chain(app.root()), vec![vec![1, 0], vec![1, 1]]);
+        assert_eq!(app.focused(), Some(&[1, 0][..]));
+        app.handle_event(&Event::key(KeyCode::Tab));
+        assert_eq!(app.focused(), Some(&[1, 1][..]));
+        app.handle_event(&Event::key(KeyCode::Tab));
+        assert_eq!(app.focused(), Some(&[1, 0][..]));
+        app.handle_event(&Event::key(KeyCode::BackTab));
+        assert_eq!(app.focused(), Some(&[1, 1][..]));
+    }
+
+    #[test]
+    fn scripted_clicks() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut app = form(&log);
+        let script = vec![
+            Event::key(KeyCode::Enter),
+            Event::key(KeyCode::Tab),
+            Event::key(KeyCode::Char(' ')),
+            Event::Key(KeyEvent::ctrl(KeyCode::Char('c'))),
+            Event::key(KeyCode::Enter),
+        ];
+        let mut out = Vec::new();
+        app.run(script, &mut out).unwrap();
+        assert_eq!(*log.borrow(), vec!["Да", "Нет"]);
+        assert!(!out.is_empty());
+    }
+
+    #[test]
+    fn mouse_focuses_and_clicks() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut app = form(&log);
+        // кнопка «Нет» начинается сразу после «Да» шириной в 12 столбцов
+        assert!(app.handle_event(&Event::click(2 + 12 + 3, 5)));
+        assert_eq!(app.focused(), Some(&[1, 1][..]));
+        assert_eq!(*log.borrow(), vec!["Нет"]);
+        assert!(!app.handle_event(&Event::click(0, 0)));
+    }
+
+    #[test]
+    fn focused_button_is_highlighted() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let app = form(&log);
+        let frame = app.frame().to_string();
+        assert!(frame.contains("|\x1b[7m    Да    \x1b[0m|"), "{}", frame);
+    }
+
+    #[test]
+    fn dialog_captures_focus_until_closed() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut app = form(&log);
+        app.focus_next();
+        let answer = Rc::new(RefCell::new(None));
+        let mut dialog = Dialog::confirm("Выход", "Точно?");
+        let slot = Rc::clone(&answer);
+        dialog.set_on_close(move |choice| *slot.borrow_mut() = Some(choice));
+        app.show_dialog(dialog);
+        assert!(app.frame().to_string().contains("Точно?"));
+
+        // основное окно недоступно, пока открыт диалог
+        assert!(!app.handle_event(&Event::click(0, 0)));
+        app.handle_event(&Event::key(KeyCode::Tab));
+        assert!(app.dialog().is_some());
+        assert!(log.borrow().is_empty());
+
+        assert!(app.handle_event(&Event::key(KeyCode::Enter)));
+        assert!(app.dialog().is_none());
+        assert_eq!(*answer.borrow(), Some(Choice::No));
+        assert_eq!(app.focused(), Some(&[1, 1][..]));
+        assert!(!app.frame().to_string().contains("Точно?"));
+    }
+
+    /// Часы, которые меняются сами по себе, без событий.
+    struct Clock {
+        time: Rc<RefCell<String>>,
+        changed: Rc<RefCell<bool>>,
+    }
+
+    impl Widget for Clock {
+        fn width(&self) -> usize {
+            5
+        }
+
+        fn height(&self) -> usize {
+            1
+        }
+
+        fn render(&self, canvas: &mut Canvas, area: Rect) {
+            canvas.put_str(area.x, area.y, &self.time.borrow(), Default::default());
+        }
+
+        fn is_dirty(&self) -> bool {
+            *self.changed.borrow()
+        }
+
+        fn mark_clean(&mut self) {
+            *self.changed.borrow_mut() = false;
+        }
+    }
+
+    #[test]
+    fn dirty_widgets_redraw_only_changes() {
+        let time = Rc::new(RefCell::new("12:00".to_owned()));
+        let changed = Rc::new(RefCell::new(false));
+        let mut window = Window::new("Часы");
+        window.add_widget(Box::new(Clock { time: Rc::clone(&time), changed: Rc::clone(&changed) }));
+        let mut app = App::new(Box::new(window));
+        let mut out = Vec::new();
+        app.present(&mut out).unwrap();
+        assert!(!app.needs_redraw());
+
+        *time.borrow_mut() = "12:01".to_owned();
+        *changed.borrow_mut() = true;
+        assert!(app.needs_redraw());
+        out.clear();
+        app.present(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1b[4;7H1");
+        assert!(!app.needs_redraw());
+
+        app.request_redraw();
+        assert!(app.needs_redraw());
+        let mut out = Vec::new();
+        app.present(&mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn resize_relayouts_and_clips() {
+        let mut window = Window::new("Широкое окно");
+        window.set_size(40, 8);
+        window.add_widget(Box::new(Label::new("Содержимое, которое шире экрана")));
+        let mut app = App::new(Box::new(window));
+        assert_eq!(app.frame().size(), Size::new(40, 8));
+
+        assert!(app.handle_event(&Event::Resize(Size::new(24, 6))));
+        let frame = app.frame();
+        assert_eq!(frame.size(), Size::new(24, 6));
+        let text = PlainRenderer { strip_borders: false }.render(&frame);
+        assert_eq!(text.lines().next(), Some("+----------------------+"));
+        assert_eq!(text.lines().nth(3), Some("| Содержимое, которое  |"));
+        assert!(text.lines().all(|line| line.chars().count() <= 24));
+    }
+}
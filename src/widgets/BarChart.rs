@@ -0,0 +1,327 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::chart::{draw_axes, draw_column, format_value, label_width, Scale, BLOCKS, BLOCKS_LEFT};
+use crate::widgets::layout::{fit, text_width, truncate, Align, Rect};
+use crate::widgets::style::{Color, Style};
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Столбчатая диаграмма с подписями. Столбцы стоят вертикально над осью
+/// или, после `set_horizontal(true)`, лежат строками вправо от подписей.
+/// Шкала по умолчанию подбирается по данным и включает ноль.
+pub struct BarChart {
+    bars: Vec<(String, f64)>,
+    /// Длина самого длинного столбца в клетках: высота вертикальной диаграммы или ширина горизонтальной.
+    length: usize,
+    horizontal: bool,
+    bar_width: usize,
+    gap: usize,
+    scale: Option<Scale>,
+    show_values: bool,
+    style: Themed<Style>,
+    axis_style: Themed<Style>,
+    label_style: Themed<Style>,
+    dirty: bool,
+}
+
+impl BarChart {
+    pub fn new(length: usize) -> BarChart {
+        BarChart {
+            bars: Vec::new(),
+            length,
+            horizontal: false,
+            bar_width: 3,
+            gap: 1,
+            scale: None,
+            show_values: true,
+            style: Themed::new(Style::new().fg(Color::Cyan)),
+            axis_style: Themed::new(Style::default()),
+            label_style: Themed::new(Style::default()),
+            dirty: false,
+        }
+    }
+
+    pub fn add_bar(&mut self, label: &str, value: f64) {
+        self.bars.push((label.to_owned(), value));
+        self.dirty = true;
+    }
+
+    pub fn bar(mut self, label: &str, value: f64) -> BarChart {
+        self.add_bar(label, value);
+        self
+    }
+
+    pub fn set_bars(&mut self, bars: &[(&str, f64)]) {
+        self.bars = bars.iter().map(|&(label, value)| (label.to_owned(), value)).collect();
+        self.dirty = true;
+    }
+
+    /// Меняет значение столбца, не трогая подпись.
+    pub fn set_value(&mut self, index: usize, value: f64) {
+        if let Some(bar) = self.bars.get_mut(index) {
+            bar.1 = value;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_horizontal(&mut self, horizontal: bool) {
+        self.horizontal = horizontal;
+    }
+
+    /// Ширина вертикального столбца в клетках; подпись под ним обрезается до неё.
+    pub fn set_bar_width(&mut self, width: usize) {
+        self.bar_width = width.max(1);
+    }
+
+    pub fn set_gap(&mut self, gap: usize) {
+        self.gap = gap;
+    }
+
+    pub fn set_scale(&mut self, scale: Option<Scale>) {
+        self.scale = scale;
+        self.dirty = true;
+    }
+
+    /// Подписывать ли значения над столбцами (справа от горизонтальных).
+    pub fn set_show_values(&mut self, show: bool) {
+        self.show_values = show;
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_axis_style(&mut self, style: Style) {
+        self.axis_style.set(style);
+    }
+
+    pub fn set_label_style(&mut self, style: Style) {
+        self.label_style.set(style);
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.scale.unwrap_or_else(|| Scale::fit(self.bars.iter().map(|&(_, value)| value), true))
+    }
+
+    fn label_column(&self) -> usize {
+        self.bars.iter().map(|(label, _)| text_width(label)).max().unwrap_or(0)
+    }
+
+    fn value_column(&self) -> usize {
+        match self.show_values {
+            true => self.bars.iter().map(|&(_, value)| text_width(&format_value(value)) + 1).max().unwrap_or(0),
+            false => 0,
+        }
+    }
+
+    fn render_vertical(&self, canvas: &mut Canvas, area: Rect) {
+        let scale = self.scale();
+        let (axis_style, label_style) = (self.axis_style.get(), self.label_style.get());
+        // нижняя строка — под подписи столбцов
+        let axes = Rect::new(area.x, area.y, area.width, area.height - 1);
+        let plot = draw_axes(canvas, axes, scale, axis_style);
+        // столбцы растут от нуля: вверх положительные, вниз отрицательные
+        let base = scale.ratio(0.0);
+        for (i, (label, value)) in self.bars.iter().enumerate() {
+            let x = plot.x + i * (self.bar_width + self.gap);
+            if x + self.bar_width > plot.right() {
+                break;
+            }
+            let rows = draw_column(canvas, x, self.bar_width, plot, base, scale.ratio(*value), self.style.get());
+            canvas.put_str(x, area.bottom() - 1, &fit(label, self.bar_width, Align::Center), label_style);
+            // подпись значения — за концом столбца, если там есть место
+            let y = match *value < 0.0 {
+                true => rows.start.checked_sub(1),
+                false => Some(rows.end).filter(|&row| row < plot.height),
+            };
+            if let Some(row) = y.filter(|_| self.show_values) {
+                let value = fit(&format_value(*value), self.bar_width, Align::Center);
+                canvas.put_str(x, plot.bottom() - 1 - row, &value, label_style);
+            }
+        }
+    }
+
+    fn render_horizontal(&self, canvas: &mut Canvas, area: Rect) {
+        let scale = self.scale();
+        let (axis_style, label_style) = (self.axis_style.get(), self.label_style.get());
+        let labels = self.label_column();
+        if area.width <= labels + 1 + self.value_column() || area.height < 2 {
+            return;
+        }
+        let x = area.x + labels + 1;
+        let length = area.width - labels - 1 - self.value_column();
+        let rows = self.bars.len().min(area.height - 2);
+        // столбцы растут от нуля: вправо положительные, влево отрицательные
+        let base = ((scale.ratio(0.0) * length as f64).round() as usize).min(length);
+        for (i, (label, value)) in self.bars.iter().take(rows).enumerate() {
+            let y = area.y + i;
+            canvas.put_str(area.x, y, &fit(label, labels, Align::Right), label_style);
+            canvas.put_border(x - 1, y, "|", axis_style);
+            let target = (scale.ratio(*value) * (length * 8) as f64).round() as usize;
+            let (start, bar) = if *value >= 0.0 {
+                let mut eighths = target.saturating_sub(base * 8);
+                if *value > 0.0 {
+                    eighths = eighths.max(1);
+                }
+                (base, BLOCKS[8].repeat(eighths / 8) + BLOCKS_LEFT[eighths % 8])
+            } else {
+                // у блоков нет правых восьмых долей: конец — половинка `▐` или черта `▕`
+                let eighths = (base * 8).saturating_sub(target).max(1);
+                let end = match eighths % 8 {
+                    0 => "",
+                    4.. => "▐",
+                    _ => "▕",
+                };
+                (base - eighths.div_ceil(8).min(base), end.to_owned() + &BLOCKS[8].repeat(eighths / 8))
+            };
+            let width = canvas.put_str(x + start, y, &bar, self.style.get());
+            if self.show_values {
+                canvas.put_str(x + start + width + 1, y, &format_value(*value), label_style);
+            }
+        }
+        let axis = area.y + rows;
+        canvas.put_border(x - 1, axis, &format!("+{}", "-".repeat(length)), axis_style);
+        let (min, max) = (format_value(scale.min), format_value(scale.max));
+        canvas.put_str(x - 1, axis + 1, &truncate(&min, length), axis_style);
+        if text_width(&min) + text_width(&max) < length {
+            canvas.put_str(x + length - text_width(&max), axis + 1, &max, axis_style);
+        }
+    }
+}
+
+impl Widget for BarChart {
+    fn width(&self) -> usize {
+        match self.horizontal {
+            true => self.label_column() + 1 + self.length + self.value_column(),
+            false => {
+                let bars = self.bars.len() * (self.bar_width + self.gap);
+                label_width(self.scale()) + 1 + bars.saturating_sub(self.gap)
+            }
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self.horizontal {
+            true => self.bars.len() + 2,
+            // строки столбцов, ось и подписи
+            false => self.length + 2,
+        }
+    }
+
+    fn accessible(&self) -> Accessible {
+        let bars: Vec<String> = self.bars.iter().map(|(label, value)| format!("{} {}", label, format_value(*value))).collect();
+        Accessible::new(AccessRole::Chart, "").with_value(bars.join(", "))
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Accent));
+        self.axis_style.theme(theme.style(Role::Border));
+        self.label_style.theme(theme.style(Role::Label));
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        if area.height < 2 {
+            return;
+        }
+        match self.horizontal {
+            true => self.render_horizontal(canvas, area),
+            false => self.render_vertical(canvas, area),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render;
+
+    fn chart() -> BarChart {
+        let mut chart = BarChart::new(4).bar("пн", 10.0).bar("вт", 25.0).bar("ср", 40.0);
+        chart.set_style(Style::default());
+        chart
+    }
+
+    #[test]
+    fn vertical_bars_with_labels() {
+        let chart = chart();
+        assert_eq!(chart.scale(), Scale::new(0.0, 40.0));
+        assert_eq!(
+            render(&chart),
+            "40|    25  ███\n\
+             \x20 |    ▄▄▄ ███\n\
+             \x20 |10  ███ ███\n\
+             \x200|███ ███ ███\n\
+             \x20 +-----------\n\
+             \x20  пн  вт  ср \n"
+        );
+    }
+
+    #[test]
+    fn horizontal_bars_with_values() {
+        let mut chart = chart();
+        chart.set_horizontal(true);
+        assert_eq!(
+            render(&chart),
+            "пн|█ 10   \n\
+             вт|██▌ 25 \n\
+             ср|████ 40\n\
+             \x20 +----   \n\
+             \x20 0  40   \n"
+        );
+        assert_eq!(chart.accessible().value.as_deref(), Some("пн 10, вт 25, ср 40"));
+    }
+
+    #[test]
+    fn negative_bars_grow_down_from_zero() {
+        let mut chart = BarChart::new(4).bar("a", -10.0).bar("b", 10.0).bar("c", 0.0).bar("d", -3.0);
+        chart.set_style(Style::default());
+        assert_eq!(chart.scale(), Scale::new(-10.0, 10.0));
+        assert_eq!(
+            render(&chart),
+            " 10|    ███        \n\
+             \x20  |    ███  0     \n\
+             \x20  |███         ▀▀▀\n\
+             -10|███         -3 \n\
+             \x20  +---------------\n\
+             \x20    a   b   c   d \n"
+        );
+        chart.set_horizontal(true);
+        assert_eq!(
+            render(&chart),
+            "a|██ -10  \n\
+             b|  ██ 10 \n\
+             c|   0    \n\
+             d| ▐ -3   \n\
+             \x20+----    \n\
+             \x20-10      \n"
+        );
+    }
+
+    #[test]
+    fn theme_styles_axes_and_labels() {
+        let theme = Theme::high_contrast();
+        let mut chart = chart();
+        chart.apply_theme(&theme);
+        let area = Rect::from_size(chart.size_hint());
+        let mut canvas = Canvas::new(area.size());
+        chart.render(&mut canvas, area);
+        assert_eq!(canvas.get(2, 0).unwrap().style, theme.style(Role::Border));
+        assert_eq!(canvas.get(4, 5).unwrap().style, theme.style(Role::Label));
+
+        let mut chart = BarChart::new(4).bar("a", 1.0);
+        chart.apply_theme(&theme);
+        let area = Rect::from_size(chart.size_hint());
+        let mut canvas = Canvas::new(area.size());
+        chart.render(&mut canvas, area);
+        let bar = canvas.rows().flatten().find(|cell| cell.symbol == "█").unwrap();
+        assert_eq!(bar.style, theme.style(Role::Accent));
+    }
+}
@@ -0,0 +1,197 @@
+/// Набор символов для рисования рамок: углы, края и стыки линий.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderStyle {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+    /// Стыки внутренних линий с внешней рамкой: ├ ┤ ┬ ┴ и перекрестье ┼.
+    pub left_tee: char,
+    pub right_tee: char,
+    pub top_tee: char,
+    pub bottom_tee: char,
+    pub cross: char,
+    /// Линия, отделяющая заголовок (в ASCII это `=`).
+    pub header: char,
+    pub header_left: char,
+    pub header_right: char,
+    pub header_cross: char,
+}
+
+impl BorderStyle {
+    pub const ASCII: BorderStyle = BorderStyle {
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        horizontal: '-',
+        vertical: '|',
+        left_tee: '+',
+        right_tee: '+',
+        top_tee: '+',
+        bottom_tee: '+',
+        cross: '+',
+        header: '=',
+        header_left: '+',
+        header_right: '+',
+        header_cross: '+',
+    };
+
+    pub const LIGHT: BorderStyle = BorderStyle {
+        top_left: '┌',
+        top_right: '┐',
+        bottom_left: '└',
+        bottom_right: '┘',
+        horizontal: '─',
+        vertical: '│',
+        left_tee: '├',
+        right_tee: '┤',
+        top_tee: '┬',
+        bottom_tee: '┴',
+        cross: '┼',
+        header: '═',
+        header_left: '╞',
+        header_right: '╡',
+        header_cross: '╪',
+    };
+
+    pub const HEAVY: BorderStyle = BorderStyle {
+        top_left: '┏',
+        top_right: '┓',
+        bottom_left: '┗',
+        bottom_right: '┛',
+        horizontal: '━',
+        vertical: '┃',
+        left_tee: '┣',
+        right_tee: '┫',
+        top_tee: '┳',
+        bottom_tee: '┻',
+        cross: '╋',
+        header: '━',
+        header_left: '┣',
+        header_right: '┫',
+        header_cross: '╋',
+    };
+
+    pub const DOUBLE: BorderStyle = BorderStyle {
+        top_left: '╔',
+        top_right: '╗',
+        bottom_left: '╚',
+        bottom_right: '╝',
+        horizontal: '═',
+        vertical: '║',
+        left_tee: '╠',
+        right_tee: '╣',
+        top_tee: '╦',
+        bottom_tee: '╩',
+        cross: '╬',
+        header: '═',
+        header_left: '╠',
+        header_right: '╣',
+        header_cross: '╬',
+    };
+
+    pub const ROUNDED: BorderStyle = BorderStyle {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        ..BorderStyle::LIGHT
+    };
+
+    /// Рамка из пробелов: место под неё сохраняется, поэтому размеры виджетов не меняются.
+    pub const NONE: BorderStyle = BorderStyle {
+        top_left: ' ',
+        top_right: ' ',
+        bottom_left: ' ',
+        bottom_right: ' ',
+        horizontal: ' ',
+        vertical: ' ',
+        left_tee: ' ',
+        right_tee: ' ',
+        top_tee: ' ',
+        bottom_tee: ' ',
+        cross: ' ',
+        header: ' ',
+        header_left: ' ',
+        header_right: ' ',
+        header_cross: ' ',
+    };
+
+    /// Встроенные рамки по именам, которые используются в файлах описаний и тем.
+    pub const PRESETS: [(&'static str, BorderStyle); 6] = [
+        ("ascii", BorderStyle::ASCII),
+        ("light", BorderStyle::LIGHT),
+        ("heavy", BorderStyle::HEAVY),
+        ("double", BorderStyle::DOUBLE),
+        ("rounded", BorderStyle::ROUNDED),
+        ("none", BorderStyle::NONE),
+    ];
+
+    pub fn named(name: &str) -> Option<BorderStyle> {
+        BorderStyle::PRESETS.iter().find(|(preset, _)| *preset == name).map(|(_, style)| *style)
+    }
+
+    /// Имя встроенной рамки; у самодельных его нет.
+    pub fn name(&self) -> Option<&'static str> {
+        BorderStyle::PRESETS.iter().find(|(_, style)| style == self).map(|(name, _)| *name)
+    }
+
+    /// Горизонтальная линия `left` + `fill` × `width` + `right`.
+    pub fn line(left: char, fill: char, width: usize, right: char) -> String {
+        let mut line = String::with_capacity(width + 2);
+        line.push(left);
+        line.extend(std::iter::repeat_n(fill, width));
+        line.push(right);
+        line
+    }
+
+    pub fn top(&self, width: usize) -> String {
+        BorderStyle::line(self.top_left, self.horizontal, width, self.top_right)
+    }
+
+    pub fn bottom(&self, width: usize) -> String {
+        BorderStyle::line(self.bottom_left, self.horizontal, width, self.bottom_right)
+    }
+
+    /// Строка содержимого между двумя вертикальными краями.
+    pub fn row(&self, content: &str) -> String {
+        format!("{}{}{}", self.vertical, content, self.vertical)
+    }
+}
+
+impl Default for BorderStyle {
+    fn default() -> BorderStyle {
+        BorderStyle::ASCII
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::layout::text_width;
+
+    #[test]
+    fn presets_are_single_width() {
+        for style in [
+            BorderStyle::ASCII,
+            BorderStyle::LIGHT,
+            BorderStyle::HEAVY,
+            BorderStyle::DOUBLE,
+            BorderStyle::ROUNDED,
+            BorderStyle::NONE,
+        ] {
+            assert_eq!(text_width(&style.top(3)), 5);
+            assert_eq!(text_width(&style.row("abc")), 5);
+            assert_eq!(text_width(&style.bottom(3)), 5);
+        }
+    }
+
+    #[test]
+    fn rounded() {
+        assert_eq!(BorderStyle::ROUNDED.top(2), "╭──╮");
+        assert_eq!(BorderStyle::ROUNDED.bottom(2), "╰──╯");
+    }
+}
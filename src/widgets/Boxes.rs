@@ -0,0 +1,409 @@
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::{cross_align_name, Value};
+use crate::widgets::layout::{Constraints, Rect, Size};
+use crate::widgets::Widget;
+
+/// Выравнивание дочерних виджетов поперёк направления контейнера.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossAlign {
+    Start,
+    Center,
+    End,
+    Stretch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    /// Раскладывает размер на (главную, поперечную) оси.
+    fn split(self, size: Size) -> (usize, usize) {
+        match self {
+            Axis::Horizontal => (size.width, size.height),
+            Axis::Vertical => (size.height, size.width),
+        }
+    }
+
+    fn join(self, main: usize, cross: usize) -> Size {
+        match self {
+            Axis::Horizontal => Size::new(main, cross),
+            Axis::Vertical => Size::new(cross, main),
+        }
+    }
+}
+
+struct Child {
+    widget: Box<dyn Widget>,
+    flex: usize,
+}
+
+/// Общая реализация `HBox` и `VBox`, отличаются они только осью.
+struct Stack {
+    axis: Axis,
+    children: Vec<Child>,
+    spacing: usize,
+    padding: usize,
+    align: CrossAlign,
+    /// Ширина, начиная с которой дети стоят в ряд; при меньшей — столбцом.
+    breakpoint: Option<usize>,
+}
+
+impl Stack {
+    fn new(axis: Axis) -> Stack {
+        Stack { axis, children: Vec::new(), spacing: 0, padding: 0, align: CrossAlign::Start, breakpoint: None }
+    }
+
+    /// Ось раскладки при доступной ширине `width`.
+    fn axis_at(&self, width: usize) -> Axis {
+        match self.breakpoint {
+            Some(breakpoint) if width < breakpoint => Axis::Vertical,
+            Some(_) => Axis::Horizontal,
+            None => self.axis,
+        }
+    }
+
+    fn gaps(&self) -> usize {
+        self.spacing * self.children.len().saturating_sub(1)
+    }
+
+    fn size_hint(&self) -> Size {
+        self.size_at(usize::MAX)
+    }
+
+    /// Размер при доступной ширине `width`. Ряд занимает не меньше ширины перехода:
+    /// родитель, который отдаст контейнеру измеренную ширину, снова получит ряд, а не столбец.
+    fn size_at(&self, width: usize) -> Size {
+        let axis = self.axis_at(width);
+        let size = self.size_along(axis);
+        match (self.breakpoint, axis) {
+            (Some(breakpoint), Axis::Horizontal) => Size::new(size.width.max(breakpoint), size.height),
+            _ => size,
+        }
+    }
+
+    fn size_along(&self, axis: Axis) -> Size {
+        let mut main = 0;
+        let mut cross = 0;
+        for child in &self.children {
+            let (m, c) = axis.split(child.widget.size_hint());
+            main += m;
+            cross = cross.max(c);
+        }
+        axis.join(main + self.gaps() + 2 * self.padding, cross + 2 * self.padding)
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        constraints.constrain(self.size_at(constraints.max_width))
+    }
+
+    /// Вычисляет размер каждого дочернего виджета и его смещение по поперечной оси.
+    fn arrange(&self, axis: Axis, size: Size) -> Vec<(Size, usize)> {
+        let (main, cross) = axis.split(size);
+        let cross = cross.saturating_sub(2 * self.padding);
+        let mut free = main.saturating_sub(2 * self.padding + self.gaps());
+
+        // Сначала место получают виджеты без веса, остаток делится между гибкими.
+        let mut mains = vec![0; self.children.len()];
+        for (i, child) in self.children.iter().enumerate().filter(|(_, c)| c.flex == 0) {
+            let limit = axis.join(free, cross);
+            let (m, _) = axis.split(child.widget.measure(Constraints::loose(limit)));
+            mains[i] = m;
+            free -= m;
+        }
+        let total_flex: usize = self.children.iter().map(|c| c.flex).sum();
+        let mut flex_seen = 0;
+        let mut flex_given = 0;
+        for (i, child) in self.children.iter().enumerate().filter(|(_, c)| c.flex > 0) {
+            flex_seen += child.flex;
+            // накопительное деление, чтобы остаток от округления не терялся
+            let share = free * flex_seen / total_flex - flex_given;
+            mains[i] = share;
+            flex_given += share;
+        }
+
+        self.children
+            .iter()
+            .zip(mains)
+            .map(|(child, m)| {
+                let c = if self.align == CrossAlign::Stretch {
+                    cross
+                } else {
+                    let limit = Constraints::loose(axis.join(m, cross));
+                    axis.split(child.widget.measure(limit)).1
+                };
+                let offset = match self.align {
+                    CrossAlign::Start | CrossAlign::Stretch => 0,
+                    CrossAlign::Center => (cross - c) / 2,
+                    CrossAlign::End => cross - c,
+                };
+                (axis.join(m, c), offset)
+            })
+            .collect()
+    }
+
+    /// Области дочерних виджетов внутри `area`.
+    fn child_areas(&self, area: Rect) -> Vec<Rect> {
+        let axis = self.axis_at(area.width);
+        let mut main = self.padding;
+        self.arrange(axis, area.size())
+            .into_iter()
+            .map(|(size, offset)| {
+                let cross = self.padding + offset;
+                let rect = match axis {
+                    Axis::Horizontal => Rect::new(area.x + main, area.y + cross, size.width, size.height),
+                    Axis::Vertical => Rect::new(area.x + cross, area.y + main, size.width, size.height),
+                };
+                main += axis.split(size).0 + self.spacing;
+                rect
+            })
+            .collect()
+    }
+
+    fn describe(&self, kind: &str) -> Option<Value> {
+        let mut properties = vec![("type", Value::from(kind))];
+        if self.spacing > 0 {
+            properties.push(("spacing", Value::from(self.spacing)));
+        }
+        if self.padding > 0 {
+            properties.push(("padding", Value::from(self.padding)));
+        }
+        if self.align != CrossAlign::Start {
+            properties.push(("align", Value::from(cross_align_name(self.align))));
+        }
+        if let Some(breakpoint) = self.breakpoint {
+            properties.push(("breakpoint", Value::from(breakpoint)));
+        }
+        let mut children = Vec::new();
+        for child in &self.children {
+            let mut description = child.widget.describe()?;
+            // вес растяжения хранится у контейнера, но записывается в описание ребёнка
+            if child.flex > 0 {
+                description.set("flex", Value::from(child.flex));
+            }
+            children.push(description);
+        }
+        properties.push(("children", Value::from(children)));
+        Some(Value::table(properties))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        for (child, rect) in self.children.iter().zip(self.child_areas(area)) {
+            canvas.render_widget(child.widget.as_ref(), rect);
+        }
+    }
+}
+
+/// Контейнер, располагающий дочерние виджеты в ряд слева направо.
+pub struct HBox {
+    stack: Stack,
+}
+
+/// Контейнер, располагающий дочерние виджеты столбцом сверху вниз.
+pub struct VBox {
+    stack: Stack,
+}
+
+/// Пошаговая сборка `HBox`/`VBox`.
+pub struct BoxBuilder<T> {
+    container: T,
+}
+
+macro_rules! box_container {
+    ($name:ident, $axis:expr, $kind:expr) => {
+        impl BoxBuilder<$name> {
+            pub fn spacing(mut self, spacing: usize) -> BoxBuilder<$name> {
+                self.container.set_spacing(spacing);
+                self
+            }
+
+            pub fn padding(mut self, padding: usize) -> BoxBuilder<$name> {
+                self.container.set_padding(padding);
+                self
+            }
+
+            pub fn align(mut self, align: CrossAlign) -> BoxBuilder<$name> {
+                self.container.set_align(align);
+                self
+            }
+
+            pub fn breakpoint(mut self, width: usize) -> BoxBuilder<$name> {
+                self.container.set_breakpoint(Some(width));
+                self
+            }
+
+            pub fn child(mut self, widget: impl Widget + 'static) -> BoxBuilder<$name> {
+                self.container.add_widget(Box::new(widget));
+                self
+            }
+
+            pub fn flex_child(mut self, widget: impl Widget + 'static, weight: usize) -> BoxBuilder<$name> {
+                self.container.add_flex(Box::new(widget), weight);
+                self
+            }
+
+            pub fn build(self) -> $name {
+                self.container
+            }
+        }
+
+        impl $name {
+            pub fn builder() -> BoxBuilder<$name> {
+                BoxBuilder { container: $name::new() }
+            }
+
+            pub fn new() -> $name {
+                $name { stack: Stack::new($axis) }
+            }
+
+            pub fn add_widget(&mut self, widget: Box<dyn Widget>) {
+                self.add_flex(widget, 0);
+            }
+
+            /// Добавляет виджет, растягиваемый вдоль оси пропорционально `weight`.
+            pub fn add_flex(&mut self, widget: Box<dyn Widget>, weight: usize) {
+                self.stack.children.push(Child { widget, flex: weight });
+            }
+
+            pub fn set_spacing(&mut self, spacing: usize) {
+                self.stack.spacing = spacing;
+            }
+
+            pub fn set_padding(&mut self, padding: usize) {
+                self.stack.padding = padding;
+            }
+
+            pub fn set_align(&mut self, align: CrossAlign) {
+                self.stack.align = align;
+            }
+
+            /// Делает контейнер отзывчивым: при ширине не меньше `width` дети стоят в ряд
+            /// шириной не меньше `width`, при меньшей — столбцом, как бы ни был создан контейнер.
+            /// `None` — всегда своя ось.
+            pub fn set_breakpoint(&mut self, width: Option<usize>) {
+                self.stack.breakpoint = width;
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name::new()
+            }
+        }
+
+        impl Widget for $name {
+            fn width(&self) -> usize {
+                self.stack.size_hint().width
+            }
+
+            fn height(&self) -> usize {
+                self.stack.size_hint().height
+            }
+
+            fn measure(&self, constraints: Constraints) -> Size {
+                self.stack.measure(constraints)
+            }
+
+            fn render(&self, canvas: &mut Canvas, area: Rect) {
+                self.stack.render(canvas, area);
+            }
+
+            fn describe(&self) -> Option<Value> {
+                self.stack.describe($kind)
+            }
+
+            fn children(&self) -> Vec<&dyn Widget> {
+                self.stack.children.iter().map(|child| child.widget.as_ref()).collect()
+            }
+
+            fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+                self.stack.children.iter_mut().map(|child| child.widget.as_mut() as &mut dyn Widget).collect()
+            }
+
+            fn child_areas(&self, area: Rect) -> Vec<Rect> {
+                self.stack.child_areas(area)
+            }
+        }
+    };
+}
+
+box_container!(HBox, Axis::Horizontal, "hbox");
+box_container!(VBox, Axis::Vertical, "vbox");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::{Button, Label};
+    use crate::widgets::snapshot::render_sized;
+
+    #[test]
+    fn two_buttons_in_a_row() {
+        let mut row = HBox::new();
+        row.set_spacing(1);
+        row.add_widget(Box::new(Button::new("Да")));
+        row.add_widget(Box::new(Button::new("Нет")));
+        assert_eq!(row.width(), 12 + 1 + 13);
+        assert_eq!(
+            render_sized(&row, row.size_hint()),
+            "+----------+ +-----------+\n\
+             |    Да    | |    Нет    |\n\
+             +----------+ +-----------+\n"
+        );
+    }
+
+    #[test]
+    fn breakpoint_stacks_narrow_rows() {
+        let mut row = HBox::new();
+        row.set_breakpoint(Some(20));
+        row.add_widget(Box::new(Label::new("левая")));
+        row.add_widget(Box::new(Label::new("правая")));
+        assert_eq!(row.measure(Constraints::loose(Size::new(80, 5))), Size::new(20, 1));
+        assert_eq!(row.measure(Constraints::loose(Size::new(19, 5))), Size::new(6, 2));
+        assert_eq!(render_sized(&row, Size::new(6, 2)), "левая \nправая\n");
+        assert_eq!(render_sized(&row, Size::new(20, 1)), "леваяправая         \n");
+    }
+
+    #[test]
+    fn breakpoint_row_inside_a_window() {
+        let mut row = HBox::new();
+        row.set_breakpoint(Some(20));
+        row.add_widget(Box::new(Label::new("левая")));
+        row.add_widget(Box::new(Label::new("правая")));
+        let mut window = crate::widgets::Window::new("");
+        window.add_widget(Box::new(row));
+        let wide = render_sized(&window, Size::new(30, 5));
+        assert_eq!(wide.lines().nth(3), Some("| леваяправая                |"));
+        let narrow = render_sized(&window, Size::new(15, 6));
+        assert_eq!(narrow.lines().skip(3).take(2).collect::<Vec<_>>(), ["| левая       |", "| правая      |"]);
+    }
+
+    #[test]
+    fn flex_shares_free_space() {
+        let mut row = HBox::new();
+        row.add_flex(Box::new(Label::new("a")), 1);
+        row.add_flex(Box::new(Label::new("b")), 2);
+        assert_eq!(render_sized(&row, Size::new(9, 1)), "a  b     \n");
+    }
+
+    #[test]
+    fn vertical_alignment_and_padding() {
+        let mut column = VBox::new();
+        column.set_padding(1);
+        column.set_spacing(1);
+        column.set_align(CrossAlign::End);
+        column.add_widget(Box::new(Label::new("ab")));
+        column.add_widget(Box::new(Label::new("c")));
+        assert_eq!(column.size_hint(), Size::new(4, 5));
+        assert_eq!(render_sized(&column, Size::new(4, 5)), "    \n ab \n    \n  c \n    \n");
+    }
+
+    #[test]
+    fn cross_center() {
+        let mut row = HBox::new();
+        row.set_align(CrossAlign::Center);
+        row.add_widget(Box::new(Label::new("x")));
+        assert_eq!(render_sized(&row, Size::new(1, 3)), " \nx\n \n");
+    }
+}
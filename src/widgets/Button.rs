@@ -1,30 +1,227 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::border::BorderStyle;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::Value;
+use crate::widgets::event::{Event, KeyCode, MouseButton, MouseKind};
+use crate::widgets::layout::{Constraints, Rect};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
 use crate::widgets::{Label, Widget};
-use std::fmt::Write;
 
 pub struct Button {
     label: Label,
+    border: Themed<BorderStyle>,
+    border_style: Themed<Style>,
+    style: Themed<Style>,
+    focus_style: Themed<Style>,
+    focused: bool,
+    on_click: Option<Box<dyn FnMut()>>,
 }
 
 impl Button {
     pub fn new(label: &str) -> Button {
-        Button { label: Label::new(label) }
+        Button {
+            label: Label::new(label),
+            border: Themed::new(BorderStyle::ASCII),
+            border_style: Themed::new(Style::default()),
+            style: Themed::new(Style::default()),
+            focus_style: Themed::new(Style::new().reverse()),
+            focused: false,
+            on_click: None,
+        }
+    }
+
+    pub fn set_border(&mut self, border: BorderStyle) {
+        self.border.set(border);
+    }
+
+    /// Стиль лицевой стороны кнопки (всё внутри рамки).
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    /// Стиль лицевой стороны, когда кнопка в фокусе.
+    pub fn set_focus_style(&mut self, style: Style) {
+        self.focus_style.set(style);
+    }
+
+    /// Обработчик нажатия: Enter или пробел на кнопке в фокусе, либо щелчок мышью.
+    pub fn set_on_click(&mut self, on_click: impl FnMut() + 'static) {
+        self.on_click = Some(Box::new(on_click));
+    }
+
+    pub fn click(&mut self) {
+        if let Some(on_click) = &mut self.on_click {
+            on_click();
+        }
+    }
+
+    fn face_style(&self) -> Style {
+        if self.focused {
+            self.focus_style.get()
+        } else {
+            self.style.get()
+        }
+    }
+}
+
+pub struct ButtonBuilder {
+    button: Button,
+}
+
+impl Button {
+    pub fn builder() -> ButtonBuilder {
+        ButtonBuilder { button: Button::new("") }
+    }
+}
+
+impl ButtonBuilder {
+    pub fn label(mut self, label: &str) -> ButtonBuilder {
+        self.button.label.set_text(label);
+        self
+    }
+
+    pub fn border(mut self, border: BorderStyle) -> ButtonBuilder {
+        self.button.set_border(border);
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> ButtonBuilder {
+        self.button.set_style(style);
+        self
+    }
+
+    pub fn focus_style(mut self, style: Style) -> ButtonBuilder {
+        self.button.set_focus_style(style);
+        self
+    }
+
+    pub fn on_click(mut self, on_click: impl FnMut() + 'static) -> ButtonBuilder {
+        self.button.set_on_click(on_click);
+        self
+    }
+
+    pub fn build(self) -> Button {
+        self.button
     }
 }
 
 impl Widget for Button {
     fn width(&self) -> usize {
-        self.label.width() + 8 // добавим немного отсутпа от границ
+        self.label.width() + 8 + 2 // добавим немного отсутпа от границ, плюс сами границы
+    }
+
+    fn height(&self) -> usize {
+        self.label.height() + 2
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::Button, self.label.text())
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        canvas.draw_box(area, &self.border.get(), self.border_style.get());
+        let face = area.inset(1, 1);
+        canvas.fill(face, " ", self.face_style());
+
+        // Надпись центрируется и по горизонтали, и по вертикали.
+        let label = self.label.measure(Constraints::loose(face.size()));
+        let x = face.x + (face.width - label.width) / 2;
+        let y = face.y + (face.height - label.height) / 2;
+        let label_area = Rect::new(x, y, label.width, label.height);
+        canvas.render_widget(&self.label, label_area);
+        canvas.set_style(label_area, self.face_style());
+    }
+
+    fn describe(&self) -> Option<Value> {
+        let mut properties = vec![("type", Value::from("button")), ("label", Value::from(self.label.text()))];
+        if self.border.get() != BorderStyle::ASCII {
+            properties.push(("border", Value::from(self.border.get().name()?)));
+        }
+        Some(Value::table(properties))
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.border.theme(theme.button_border);
+        self.border_style.theme(theme.style(Role::Border));
+        self.style.theme(theme.style(Role::Button));
+        self.focus_style.theme(theme.style(Role::ButtonFocused));
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
     }
 
-    fn draw_into(&self, buffer: &mut dyn Write) {
-        let width = self.width();
-        let mut label = String::new();
-        self.label.draw_into(&mut label);
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let pressed = match event {
+            Event::Key(key) => key.is(KeyCode::Enter) || key.is(KeyCode::Char(' ')),
+            Event::Mouse(mouse) => mouse.kind == MouseKind::Press(MouseButton::Left),
+            Event::Resize(_) => false,
+        };
+        if pressed {
+            self.click();
+        }
+        pressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::layout::{text_width, Size};
+    use crate::widgets::snapshot::{render, render_sized};
 
-        writeln!(buffer, "+{:-<width$}+", "-").unwrap();
-        for line in label.lines() {
-            writeln!(buffer, "|{:^width$}|", &line).unwrap();
+    #[test]
+    fn border_matches_width() {
+        for label in ["OK", "確認", "Готово 👍🏽", "cafe\u{301}"] {
+            let button = Button::new(label);
+            let out = render(&button);
+            for line in out.lines() {
+                assert_eq!(text_width(line), button.width(), "{}", out);
+            }
+            assert_eq!(out.lines().count(), button.height());
         }
-        writeln!(buffer, "+{:-<width$}+", "-").unwrap();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn fixed_size() {
+        let button = Button::new("OK");
+        let out = render_sized(&button, Size::new(6, 5));
+        assert_eq!(out, "+----+\n|    |\n| OK |\n|    |\n+----+\n");
+    }
+
+    #[test]
+    fn rounded_border() {
+        let mut button = Button::new("OK");
+        button.set_border(BorderStyle::ROUNDED);
+        let out = render_sized(&button, Size::new(6, 3));
+        assert_eq!(out, "╭────╮\n│ OK │\n╰────╯\n");
+    }
+
+    #[test]
+    fn click_handlers() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let clicks = Rc::new(Cell::new(0));
+        let mut button = Button::new("OK");
+        let counter = Rc::clone(&clicks);
+        button.set_on_click(move || counter.set(counter.get() + 1));
+        assert!(button.handle_event(&Event::key(KeyCode::Enter)));
+        assert!(button.handle_event(&Event::key(KeyCode::Char(' '))));
+        assert!(!button.handle_event(&Event::key(KeyCode::Char('x'))));
+        assert_eq!(clicks.get(), 2);
+    }
+
+    #[test]
+    fn highlighted_face() {
+        let mut button = Button::new("OK");
+        button.set_style(Style::new().reverse());
+        let out = render_sized(&button, Size::new(6, 3));
+        assert_eq!(out, "+----+\n|␛[7m OK ␛[0m|\n+----+\n");
+    }
+}
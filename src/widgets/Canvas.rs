@@ -0,0 +1,317 @@
+use std::fmt;
+use std::io;
+use crate::widgets::border::BorderStyle;
+use crate::widgets::layout::{Rect, Size};
+use crate::widgets::style::{Style, RESET};
+use crate::widgets::unicode::{grapheme_width, graphemes};
+use crate::widgets::Widget;
+
+/// Одна клетка экрана. Широкий символ занимает две клетки: во второй
+/// остаётся пустой `symbol`, и при выводе она пропускается.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: String,
+    pub style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Cell {
+        Cell { symbol: " ".to_owned(), style: Style::default() }
+    }
+}
+
+impl Cell {
+    /// Вторая половина широкого символа.
+    pub fn is_continuation(&self) -> bool {
+        self.symbol.is_empty()
+    }
+}
+
+/// Экранный буфер из оформленных клеток с адресацией по `x, y`.
+/// Все операции записи обрезаются по текущему прямоугольнику отсечения.
+#[derive(Clone)]
+pub struct Canvas {
+    size: Size,
+    cells: Vec<Cell>,
+    /// Клетки, занятые рамками и разделителями, — их можно убрать при экспорте.
+    borders: Vec<bool>,
+    clip: Vec<Rect>,
+}
+
+impl Canvas {
+    pub fn new(size: Size) -> Canvas {
+        let count = size.width * size.height;
+        Canvas { size, cells: vec![Cell::default(); count], borders: vec![false; count], clip: Vec::new() }
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn area(&self) -> Rect {
+        Rect::from_size(self.size)
+    }
+
+    /// Текущая область отсечения.
+    pub fn clip(&self) -> Rect {
+        self.clip.last().copied().unwrap_or_else(|| self.area())
+    }
+
+    /// Сужает область отсечения до пересечения с `rect` до вызова `pop_clip`.
+    pub fn push_clip(&mut self, rect: Rect) {
+        let clip = self.clip().intersect(rect);
+        self.clip.push(clip);
+    }
+
+    pub fn pop_clip(&mut self) {
+        self.clip.pop();
+    }
+
+    /// Рисует дочерний виджет в его области; за её пределы виджет не выйдет.
+    pub fn render_widget(&mut self, widget: &dyn Widget, area: Rect) {
+        self.push_clip(area);
+        widget.render(self, area);
+        self.pop_clip();
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        if x < self.size.width && y < self.size.height {
+            Some(&self.cells[y * self.size.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Клетка нарисована как часть рамки (`draw_box`, `put_border`).
+    pub fn is_border(&self, x: usize, y: usize) -> bool {
+        x < self.size.width && y < self.size.height && self.borders[y * self.size.width + x]
+    }
+
+    /// Строки клеток сверху вниз.
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.size.width.max(1)).take(self.size.height)
+    }
+
+    fn cell_mut(&mut self, x: usize, y: usize) -> &mut Cell {
+        &mut self.cells[y * self.size.width + x]
+    }
+
+    /// Записывает графему в клетку. Возвращает её ширину, даже если она отсечена.
+    pub fn set(&mut self, x: usize, y: usize, symbol: &str, style: Style) -> usize {
+        let width = grapheme_width(symbol);
+        let clip = self.clip();
+        if width == 0 || !clip.contains(x, y) {
+            return width;
+        }
+        // Широкий символ, не помещающийся целиком, заменяется пробелом.
+        let symbol = if width == 2 && !clip.contains(x + 1, y) { " " } else { symbol };
+
+        // Затираем половинки широких символов, которые перекрываются новой записью.
+        if self.cell_mut(x, y).is_continuation() && x > 0 {
+            *self.cell_mut(x - 1, y) = Cell { symbol: " ".to_owned(), style: Style::default() };
+        }
+        let end = x + grapheme_width(symbol);
+        if end < self.size.width && self.cell_mut(end, y).is_continuation() {
+            *self.cell_mut(end, y) = Cell { symbol: " ".to_owned(), style: Style::default() };
+        }
+
+        *self.cell_mut(x, y) = Cell { symbol: symbol.to_owned(), style };
+        self.borders[y * self.size.width + x] = false;
+        if grapheme_width(symbol) == 2 {
+            *self.cell_mut(x + 1, y) = Cell { symbol: String::new(), style };
+            self.borders[y * self.size.width + x + 1] = false;
+        }
+        width
+    }
+
+    /// Пишет строку начиная с `x, y`; escape-последовательности пропускаются.
+    /// Возвращает ширину строки в столбцах.
+    pub fn put_str(&mut self, x: usize, y: usize, text: &str, style: Style) -> usize {
+        let mut column = x;
+        for grapheme in graphemes(text) {
+            if grapheme.starts_with('\x1b') {
+                continue;
+            }
+            column += self.set(column, y, grapheme, style);
+        }
+        column - x
+    }
+
+    /// Как `put_str`, но клетки помечаются как рамка.
+    pub fn put_border(&mut self, x: usize, y: usize, text: &str, style: Style) -> usize {
+        let width = self.put_str(x, y, text, style);
+        let clip = self.clip();
+        for column in x..x + width {
+            if clip.contains(column, y) {
+                self.borders[y * self.size.width + column] = true;
+            }
+        }
+        width
+    }
+
+    /// Заполняет область одной графемой.
+    pub fn fill(&mut self, rect: Rect, symbol: &str, style: Style) {
+        for y in rect.y..rect.bottom() {
+            for x in rect.x..rect.right() {
+                self.set(x, y, symbol, style);
+            }
+        }
+    }
+
+    /// Меняет оформление клеток области, не трогая их содержимое.
+    pub fn set_style(&mut self, rect: Rect, style: Style) {
+        let rect = rect.intersect(self.clip());
+        for y in rect.y..rect.bottom() {
+            for x in rect.x..rect.right() {
+                self.cell_mut(x, y).style = style;
+            }
+        }
+    }
+
+    /// Тень под всплывающей областью: полоса в два столбца справа и строка снизу
+    /// со сдвигом, содержимое под ними остаётся видно приглушённым.
+    pub fn shadow(&mut self, rect: Rect) {
+        let style = Style::new().dim();
+        self.set_style(Rect::new(rect.right(), rect.y + 1, 2, rect.height), style);
+        self.set_style(Rect::new(rect.x + 2, rect.bottom(), rect.width, 1), style);
+    }
+
+    /// Рисует рамку по краю области.
+    pub fn draw_box(&mut self, rect: Rect, border: &BorderStyle, style: Style) {
+        if rect.width < 2 || rect.height < 2 {
+            return;
+        }
+        let inner = rect.width - 2;
+        self.put_border(rect.x, rect.y, &border.top(inner), style);
+        for y in rect.y + 1..rect.bottom() - 1 {
+            self.put_border(rect.x, y, &border.vertical.to_string(), style);
+            self.put_border(rect.right() - 1, y, &border.vertical.to_string(), style);
+        }
+        self.put_border(rect.x, rect.bottom() - 1, &border.bottom(inner), style);
+    }
+
+    /// Выводит буфер на терминал поверх текущего экрана, начиная с левого верхнего угла.
+    pub fn flush(&self, out: &mut dyn io::Write) -> io::Result<()> {
+        write!(out, "\x1b[H")?;
+        for (y, line) in self.to_string().lines().enumerate() {
+            if y > 0 {
+                write!(out, "\r\n")?;
+            }
+            write!(out, "{}", line)?;
+        }
+        out.flush()
+    }
+
+    /// Вывод, превращающий на терминале кадр `previous` в этот: перемещения курсора
+    /// и только изменившиеся клетки. Кадры должны быть одного размера.
+    pub fn diff(&self, previous: &Canvas) -> String {
+        let mut out = String::new();
+        let mut cursor: Option<(usize, usize)> = None;
+        let mut current = Style::default();
+        for y in 0..self.size.height {
+            for x in 0..self.size.width {
+                let cell = &self.cells[y * self.size.width + x];
+                if cell.is_continuation() {
+                    continue;
+                }
+                // широкий символ сравнивается вместе со второй половиной
+                let width = grapheme_width(&cell.symbol);
+                if (x..x + width).all(|column| previous.get(column, y) == self.get(column, y)) {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    out.push_str(&format!("\x1b[{};{}H", y + 1, x + 1));
+                }
+                if cell.style != current {
+                    if !current.is_plain() {
+                        out.push_str(RESET);
+                    }
+                    out.push_str(&cell.style.prefix());
+                    current = cell.style;
+                }
+                out.push_str(&cell.symbol);
+                cursor = Some((x + width, y));
+            }
+        }
+        if !current.is_plain() {
+            out.push_str(RESET);
+        }
+        out
+    }
+}
+
+/// Построчный вывод с escape-последовательностями там, где меняется стиль.
+impl fmt::Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            let mut current = Style::default();
+            for cell in row.iter().filter(|cell| !cell.is_continuation()) {
+                if cell.style != current {
+                    if !current.is_plain() {
+                        f.write_str(RESET)?;
+                    }
+                    f.write_str(&cell.style.prefix())?;
+                    current = cell.style;
+                }
+                f.write_str(&cell.symbol)?;
+            }
+            if !current.is_plain() {
+                f.write_str(RESET)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::style::Color;
+    use crate::widgets::{Button, Label};
+
+    #[test]
+    fn clipping() {
+        let mut canvas = Canvas::new(Size::new(6, 2));
+        canvas.push_clip(Rect::new(1, 0, 3, 1));
+        canvas.put_str(0, 0, "abcdef", Style::default());
+        canvas.put_str(0, 1, "zzz", Style::default());
+        canvas.pop_clip();
+        assert_eq!(canvas.to_string(), " bcd  \n      \n");
+    }
+
+    #[test]
+    fn wide_characters() {
+        let mut canvas = Canvas::new(Size::new(5, 1));
+        assert_eq!(canvas.put_str(0, 0, "日本語", Style::default()), 6);
+        assert_eq!(canvas.to_string(), "日本 \n");
+        canvas.put_str(1, 0, "x", Style::default());
+        assert_eq!(canvas.to_string(), " x本 \n");
+    }
+
+    #[test]
+    fn styled_output() {
+        let mut canvas = Canvas::new(Size::new(4, 1));
+        let red = Style::new().fg(Color::Red);
+        canvas.put_str(1, 0, "ab", red);
+        assert_eq!(canvas.to_string(), " \x1b[31mab\x1b[0m \n");
+    }
+
+    #[test]
+    fn nested_clips_intersect() {
+        let mut canvas = Canvas::new(Size::new(4, 4));
+        canvas.push_clip(Rect::new(0, 0, 2, 2));
+        canvas.push_clip(Rect::new(1, 1, 3, 3));
+        assert_eq!(canvas.clip(), Rect::new(1, 1, 1, 1));
+        canvas.pop_clip();
+        assert_eq!(canvas.clip(), Rect::new(0, 0, 2, 2));
+    }
+
+    #[test]
+    fn widgets_at_offset_overlap() {
+        let mut canvas = Canvas::new(Size::new(10, 4));
+        canvas.render_widget(&Label::new("..........\n..........\n.........."), Rect::new(0, 0, 10, 3));
+        canvas.render_widget(&Button::new("OK"), Rect::new(3, 1, 6, 3));
+        assert_eq!(canvas.to_string(), "..........\n...+----+.\n...| OK |.\n   +----+ \n");
+    }
+}
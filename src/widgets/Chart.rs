@@ -0,0 +1,197 @@
+use std::ops::Range;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::layout::{fit, text_width, Align, Rect};
+use crate::widgets::style::Style;
+
+/// Восьмые доли клетки снизу вверх: пусто, `▁` … `█`.
+pub const BLOCKS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// Восьмые доли клетки слева направо: пусто, `▏` … `▉`.
+pub const BLOCKS_LEFT: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// Диапазон значений оси. Графики подбирают его по данным (`Scale::fit`),
+/// если не задан явно.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Scale {
+    pub fn new(min: f64, max: f64) -> Scale {
+        Scale { min, max }
+    }
+
+    /// Наименьший «круглый» диапазон, вмещающий все конечные значения.
+    /// `include_zero` тянет шкалу до нуля, как нужно столбцам.
+    pub fn fit(values: impl IntoIterator<Item = f64>, include_zero: bool) -> Scale {
+        let (mut min, mut max) = values
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+        if min > max {
+            return Scale::new(0.0, 1.0);
+        }
+        if include_zero {
+            min = min.min(0.0);
+            max = max.max(0.0);
+        }
+        if min == max {
+            // ровный ряд рисуется посередине
+            let margin = if max == 0.0 { 1.0 } else { max.abs() / 10.0 };
+            min -= margin;
+            max += margin;
+        }
+        let step = nice_step((max - min) / 4.0);
+        Scale::new((min / step).floor() * step, (max / step).ceil() * step)
+    }
+
+    /// Положение значения на шкале от 0 до 1; выходящие за шкалу значения прижимаются к краю.
+    pub fn ratio(&self, value: f64) -> f64 {
+        if self.max <= self.min || !value.is_finite() {
+            return 0.0;
+        }
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+}
+
+/// Шаг делений 1, 2 или 5, умноженный на степень десяти, не меньше `raw`.
+fn nice_step(raw: f64) -> f64 {
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let fraction = raw / magnitude;
+    let nice = match fraction {
+        f if f <= 1.0 => 1.0,
+        f if f <= 2.0 => 2.0,
+        f if f <= 5.0 => 5.0,
+        _ => 10.0,
+    };
+    nice * magnitude
+}
+
+/// Короткая подпись значения: `12`, `0.25`, `3.1`, `12k`, `4.5M`.
+pub fn format_value(value: f64) -> String {
+    let (scaled, suffix) = match value.abs() {
+        abs if abs >= 1e9 => (value / 1e9, "G"),
+        abs if abs >= 1e6 => (value / 1e6, "M"),
+        abs if abs >= 1e4 => (value / 1e3, "k"),
+        _ => (value, ""),
+    };
+    let decimals = match scaled.abs() {
+        abs if abs >= 100.0 => 0,
+        abs if abs >= 10.0 => 1,
+        _ => 2,
+    };
+    let mut text = format!("{:.*}", decimals, scaled);
+    if text.contains('.') {
+        text = text.trim_end_matches('0').trim_end_matches('.').to_owned();
+    }
+    if text == "-0" {
+        text = "0".to_owned();
+    }
+    text + suffix
+}
+
+/// Ширина подписей шкалы слева от оси значений.
+pub fn label_width(scale: Scale) -> usize {
+    text_width(&format_value(scale.min)).max(text_width(&format_value(scale.max)))
+}
+
+/// Рисует ось значений с подписями слева и ось снизу; возвращает область графика внутри осей.
+/// Подписаны верх и низ шкалы, а на высоких графиках — и середина.
+pub fn draw_axes(canvas: &mut Canvas, area: Rect, scale: Scale, style: Style) -> Rect {
+    let labels = label_width(scale);
+    if area.width <= labels + 1 || area.height < 2 {
+        return Rect::new(area.x, area.y, 0, 0);
+    }
+    let plot = Rect::new(area.x + labels + 1, area.y, area.width - labels - 1, area.height - 1);
+    for y in plot.y..plot.bottom() {
+        canvas.put_border(plot.x - 1, y, "|", style);
+    }
+    canvas.put_border(plot.x - 1, plot.bottom(), &format!("+{}", "-".repeat(plot.width)), style);
+    let mut marks = vec![(plot.y, scale.max), (plot.bottom() - 1, scale.min)];
+    if plot.height >= 5 {
+        let middle = plot.height / 2;
+        let value = scale.max - (scale.max - scale.min) * middle as f64 / (plot.height - 1) as f64;
+        marks.push((plot.y + middle, value));
+    }
+    for (y, value) in marks {
+        canvas.put_str(area.x, y, &fit(&format_value(value), labels, Align::Right), style);
+    }
+    plot
+}
+
+/// Столбец шириной `width` клеток от уровня `base` до уровня `ratio` (доли высоты `plot`).
+/// Уровень `base` округляется до границы клеток. Вверх столбец растёт с точностью до восьмой доли клетки,
+/// вниз — целыми клетками и половинкой `▀` или чертой `▔` на конце: верхних восьмых долей у блоков нет.
+/// Ненулевой столбец виден хотя бы чертой. Возвращает занятые строки, считая от низа `plot`.
+pub fn draw_column(
+    canvas: &mut Canvas,
+    x: usize,
+    width: usize,
+    plot: Rect,
+    base: f64,
+    ratio: f64,
+    style: Style,
+) -> Range<usize> {
+    let base_row = ((base * plot.height as f64).round() as usize).min(plot.height);
+    let target = (ratio * (plot.height * 8) as f64).round() as usize;
+    if ratio >= base {
+        let mut eighths = target.saturating_sub(base_row * 8);
+        if ratio > base {
+            eighths = eighths.max(1);
+        }
+        let rows = eighths.div_ceil(8).min(plot.height - base_row);
+        for row in 0..rows {
+            let filled = (eighths - row * 8).min(8);
+            canvas.put_str(x, plot.bottom() - 1 - base_row - row, &BLOCKS[filled].repeat(width), style);
+        }
+        base_row..base_row + rows
+    } else {
+        let eighths = (base_row * 8).saturating_sub(target).max(1);
+        let rows = eighths.div_ceil(8).min(base_row);
+        for row in 0..rows {
+            let symbol = match (eighths - row * 8).min(8) {
+                8 => BLOCKS[8],
+                4.. => "▀",
+                _ => "▔",
+            };
+            canvas.put_str(x, plot.bottom() - base_row + row, &symbol.repeat(width), style);
+        }
+        base_row - rows..base_row
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::layout::Size;
+
+    #[test]
+    fn scale_rounds_outward() {
+        assert_eq!(Scale::fit([3.0, 37.0], true), Scale::new(0.0, 40.0));
+        assert_eq!(Scale::fit([3.0, 37.0], false), Scale::new(0.0, 40.0));
+        assert_eq!(Scale::fit([15.0, 85.0], false), Scale::new(0.0, 100.0));
+        assert_eq!(Scale::fit([-3.0, 7.0], false), Scale::new(-5.0, 10.0));
+        assert_eq!(Scale::fit([5.0, 5.0], false), Scale::new(4.5, 5.5));
+        assert_eq!(Scale::fit([], true), Scale::new(0.0, 1.0));
+        assert_eq!(Scale::new(0.0, 40.0).ratio(50.0), 1.0);
+    }
+
+    #[test]
+    fn value_labels() {
+        let labels: Vec<String> = [0.0, 12.0, 0.25, 1.23456, 12.345, 1500.0, 12000.0, 4_500_000.0, -0.001].into_iter().map(format_value).collect();
+        assert_eq!(labels, ["0", "12", "0.25", "1.23", "12.3", "1500", "12k", "4.5M", "0"]);
+    }
+
+    #[test]
+    fn axes_and_columns() {
+        let mut canvas = Canvas::new(Size::new(8, 4));
+        let plot = draw_axes(&mut canvas, Rect::new(0, 0, 8, 4), Scale::new(0.0, 10.0), Style::default());
+        assert_eq!(plot, Rect::new(3, 0, 5, 3));
+        assert_eq!(draw_column(&mut canvas, 4, 2, plot, 0.0, 0.5, Style::default()), 0..2);
+        // от середины почти до низа: целая клетка и половинка
+        assert_eq!(draw_column(&mut canvas, 7, 1, plot, 0.5, 0.1, Style::default()), 0..2);
+        let rows: Vec<String> = canvas.rows().map(|row| row.iter().map(|cell| cell.symbol.as_str()).collect()).collect();
+        assert_eq!(rows, ["10|     ", "  | ▄▄ █", " 0| ██ ▀", "  +-----"]);
+    }
+}
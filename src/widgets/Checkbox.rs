@@ -0,0 +1,138 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::Value;
+use crate::widgets::event::{Event, KeyCode, MouseButton, MouseKind};
+use crate::widgets::layout::{text_width, Rect};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Флажок `[x] надпись`; Enter, пробел или щелчок переключают его.
+pub struct Checkbox {
+    label: String,
+    checked: bool,
+    style: Themed<Style>,
+    focus_style: Themed<Style>,
+    focused: bool,
+    on_change: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Checkbox {
+    pub fn new(label: &str) -> Checkbox {
+        Checkbox {
+            label: label.to_owned(),
+            checked: false,
+            style: Themed::new(Style::default()),
+            focus_style: Themed::new(Style::new().reverse()),
+            focused: false,
+            on_change: None,
+        }
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Меняет состояние без вызова обработчика.
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_focus_style(&mut self, style: Style) {
+        self.focus_style.set(style);
+    }
+
+    pub fn set_on_change(&mut self, on_change: impl FnMut(bool) + 'static) {
+        self.on_change = Some(Box::new(on_change));
+    }
+
+    pub fn toggle(&mut self) {
+        self.checked = !self.checked;
+        if let Some(on_change) = &mut self.on_change {
+            on_change(self.checked);
+        }
+    }
+}
+
+impl Widget for Checkbox {
+    fn width(&self) -> usize {
+        text_width(&self.label) + 4
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::CheckBox, &self.label).with_value(if self.checked { "отмечен" } else { "не отмечен" })
+    }
+
+    fn describe(&self) -> Option<Value> {
+        let mut properties = vec![("type", Value::from("checkbox")), ("label", Value::from(self.label.as_str()))];
+        if self.checked {
+            properties.push(("checked", Value::from(true)));
+        }
+        Some(Value::table(properties))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let mark = if self.checked { "[x]" } else { "[ ]" };
+        let style = if self.focused { self.focus_style.get() } else { self.style.get() };
+        canvas.put_str(area.x, area.y, mark, style);
+        canvas.put_str(area.x + 4, area.y, &self.label, self.style.get());
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Label));
+        self.focus_style.theme(theme.style(Role::ButtonFocused));
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let pressed = match event {
+            Event::Key(key) => key.is(KeyCode::Enter) || key.is(KeyCode::Char(' ')),
+            Event::Mouse(mouse) => mouse.kind == MouseKind::Press(MouseButton::Left),
+            Event::Resize(_) => false,
+        };
+        let before = self.checked;
+        if pressed {
+            self.toggle();
+        }
+        self.checked != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn toggles_and_notifies() {
+        let seen = Rc::new(Cell::new(None));
+        let mut checkbox = Checkbox::new("Запомнить");
+        let sink = Rc::clone(&seen);
+        checkbox.set_on_change(move |checked| sink.set(Some(checked)));
+
+        assert_eq!(render(&checkbox), "[ ] Запомнить\n");
+
+        assert!(checkbox.handle_event(&Event::key(KeyCode::Char(' '))));
+        assert!(checkbox.is_checked());
+        assert_eq!(seen.get(), Some(true));
+        assert_eq!(render(&checkbox), "[x] Запомнить\n");
+        assert!(!checkbox.handle_event(&Event::key(KeyCode::Char('a'))));
+    }
+}
@@ -0,0 +1,230 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::widgets::boxes::HBox;
+use crate::widgets::button::Button;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::event::{Event, KeyCode};
+use crate::widgets::label::Label;
+use crate::widgets::layout::{Constraints, Rect, Size, Wrap};
+use crate::widgets::text_field::TextField;
+use crate::widgets::theme::Theme;
+use crate::widgets::window::Window;
+use crate::widgets::Widget;
+
+/// Чем закрыт диалог.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Choice {
+    Ok,
+    Yes,
+    No,
+    Cancel,
+    /// Подтверждённый ввод в диалоге-запросе.
+    Input(String),
+}
+
+/// Модальное окно: сообщение, вопрос «да/нет» или запрос строки.
+/// Показывается поверх основного окна через `App::show_dialog`, забирает фокус
+/// до закрытия и передаёт выбор пользователя в `on_close`.
+pub struct Dialog {
+    window: Window,
+    /// Выбор, сделанный кнопкой или клавишей; общий с обработчиками кнопок.
+    choice: Rc<RefCell<Option<Choice>>>,
+    /// Текст поля ввода, если это запрос.
+    input: Option<Rc<RefCell<String>>>,
+    /// Что означают Enter и Esc, если их не поглотил виджет в фокусе.
+    accept: Choice,
+    on_close: Option<Box<dyn FnMut(Choice)>>,
+}
+
+/// Ширина, по которой переносится текст сообщения.
+const MESSAGE_WIDTH: usize = 40;
+
+impl Dialog {
+    fn new(title: &str, message: &str, accept: Choice) -> Dialog {
+        let mut window = Window::new(title);
+        let mut label = Label::new(message);
+        label.set_wrap(Wrap::Word);
+        label.set_max_width(Some(MESSAGE_WIDTH));
+        window.add_widget(Box::new(label));
+        Dialog { window, choice: Rc::new(RefCell::new(None)), input: None, accept, on_close: None }
+    }
+
+    fn add_buttons(&mut self, buttons: &[(&str, Choice)]) {
+        let mut row = HBox::new();
+        row.set_spacing(1);
+        for (label, choice) in buttons {
+            let mut button = Button::new(label);
+            let (slot, choice) = (Rc::clone(&self.choice), choice.clone());
+            button.set_on_click(move || *slot.borrow_mut() = Some(choice.clone()));
+            row.add_widget(Box::new(button));
+        }
+        self.window.add_widget(Box::new(row));
+    }
+
+    /// Сообщение с единственной кнопкой «OK».
+    pub fn message(title: &str, message: &str) -> Dialog {
+        let mut dialog = Dialog::new(title, message, Choice::Ok);
+        dialog.add_buttons(&[("OK", Choice::Ok)]);
+        dialog
+    }
+
+    /// Вопрос с кнопками «Да» и «Нет»; Esc отвечает «Нет».
+    pub fn confirm(title: &str, message: &str) -> Dialog {
+        let mut dialog = Dialog::new(title, message, Choice::Yes);
+        dialog.add_buttons(&[("Да", Choice::Yes), ("Нет", Choice::No)]);
+        dialog
+    }
+
+    /// Запрос строки: поле ввода с начальным текстом и кнопки «OK» и «Отмена».
+    pub fn prompt(title: &str, message: &str, initial: &str) -> Dialog {
+        let mut dialog = Dialog::new(title, message, Choice::Ok);
+        let input = Rc::new(RefCell::new(initial.to_owned()));
+        let mut field = TextField::new(MESSAGE_WIDTH / 2);
+        field.set_text(initial);
+        let text = Rc::clone(&input);
+        field.set_on_change(move |value| *text.borrow_mut() = value.to_owned());
+        dialog.window.add_widget(Box::new(field));
+        dialog.input = Some(input);
+        dialog.add_buttons(&[("OK", Choice::Ok), ("Отмена", Choice::Cancel)]);
+        dialog
+    }
+
+    pub fn set_on_close(&mut self, on_close: impl FnMut(Choice) + 'static) {
+        self.on_close = Some(Box::new(on_close));
+    }
+
+    /// Выбор пользователя; `None`, пока диалог открыт. «OK» в запросе возвращает введённый текст.
+    pub fn choice(&self) -> Option<Choice> {
+        let choice = self.choice.borrow().clone()?;
+        Some(match (choice, &self.input) {
+            (Choice::Ok, Some(input)) => Choice::Input(input.borrow().clone()),
+            (choice, _) => choice,
+        })
+    }
+
+    /// Закрывает диалог с заданным выбором.
+    pub fn dismiss(&mut self, choice: Choice) {
+        *self.choice.borrow_mut() = Some(choice);
+    }
+
+    /// Сообщает выбор в `on_close`; вызывается один раз, когда диалог убирают с экрана.
+    pub fn close(&mut self) {
+        if let (Some(choice), Some(on_close)) = (self.choice(), &mut self.on_close) {
+            on_close(choice);
+        }
+    }
+
+    /// Область диалога над областью `over`: по центру, в собственном размере.
+    pub fn area_over(&self, over: Rect) -> Rect {
+        over.centered(self.measure(Constraints::loose(over.size())))
+    }
+
+    /// Рисует диалог поверх уже нарисованного содержимого `over`: с тенью и по центру.
+    pub fn present(&self, canvas: &mut Canvas, over: Rect) {
+        let area = self.area_over(over);
+        canvas.push_clip(over);
+        canvas.shadow(area);
+        canvas.pop_clip();
+        canvas.fill(area, " ", Default::default());
+        canvas.render_widget(self, area);
+    }
+}
+
+impl Widget for Dialog {
+    fn width(&self) -> usize {
+        self.window.width()
+    }
+
+    fn height(&self) -> usize {
+        self.window.height()
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.window.measure(constraints)
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        self.window.render(canvas, area);
+    }
+
+    /// Сюда доходят клавиши, которые не поглотил виджет в фокусе:
+    /// Enter подтверждает, Esc отменяет.
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::Key(key) = event else {
+            return false;
+        };
+        let choice = match key.code {
+            KeyCode::Enter => self.accept.clone(),
+            KeyCode::Esc if self.accept == Choice::Yes => Choice::No,
+            KeyCode::Esc => Choice::Cancel,
+            _ => return false,
+        };
+        self.dismiss(choice);
+        true
+    }
+
+    fn children(&self) -> Vec<&dyn Widget> {
+        vec![&self.window]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        vec![&mut self.window]
+    }
+
+    fn child_areas(&self, area: Rect) -> Vec<Rect> {
+        vec![area]
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.window.apply_theme(theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buttons_and_keys_choose() {
+        let mut dialog = Dialog::confirm("Выход", "Сохранить изменения?");
+        assert_eq!(dialog.choice(), None);
+        dialog.handle_event(&Event::key(KeyCode::Esc));
+        assert_eq!(dialog.choice(), Some(Choice::No));
+
+        let mut dialog = Dialog::message("Готово", "Файл сохранён");
+        let row = &mut dialog.window.children_mut()[1];
+        row.children_mut()[0].handle_event(&Event::key(KeyCode::Enter));
+        assert_eq!(dialog.choice(), Some(Choice::Ok));
+    }
+
+    #[test]
+    fn prompt_returns_text() {
+        let result = Rc::new(RefCell::new(None));
+        let mut dialog = Dialog::prompt("Имя", "Как назвать файл?", "отчёт");
+        let slot = Rc::clone(&result);
+        dialog.set_on_close(move |choice| *slot.borrow_mut() = Some(choice));
+        dialog.window.children_mut()[1].handle_event(&Event::key(KeyCode::Char('2')));
+        dialog.handle_event(&Event::key(KeyCode::Enter));
+        dialog.close();
+        assert_eq!(*result.borrow(), Some(Choice::Input("отчёт2".to_owned())));
+    }
+
+    #[test]
+    fn presented_over_content() {
+        let mut canvas = Canvas::new(Size::new(30, 11));
+        canvas.fill(canvas.area(), ".", Default::default());
+        let dialog = Dialog::message("Ок", "Всё");
+        let screen = canvas.area();
+        assert_eq!(dialog.area_over(screen), Rect::new(7, 1, 16, 8));
+        dialog.present(&mut canvas, screen);
+        let text = canvas.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "..............................");
+        assert!(lines[1].starts_with(".......+--------------+"), "{}", text);
+        // тень приглушает клетки справа и снизу, не стирая их
+        assert!(canvas.get(23, 2).unwrap().style.dim);
+        assert!(canvas.get(9, 9).unwrap().style.dim);
+        assert!(!canvas.get(8, 9).unwrap().style.dim);
+        assert_eq!(canvas.get(9, 9).unwrap().symbol, ".");
+    }
+}
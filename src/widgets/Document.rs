@@ -0,0 +1,723 @@
+use std::fmt;
+use crate::widgets::access::Accessible;
+use crate::widgets::border::BorderStyle;
+use crate::widgets::boxes::{CrossAlign, HBox, VBox};
+use crate::widgets::button::Button;
+use crate::widgets::checkbox::Checkbox;
+use crate::widgets::label::Label;
+use crate::widgets::layout::{Align, Wrap};
+use crate::widgets::radio_group::RadioGroup;
+use crate::widgets::table::{ColumnWidth, Table};
+use crate::widgets::text_area::TextArea;
+use crate::widgets::text_field::TextField;
+use crate::widgets::toggle::Toggle;
+use crate::widgets::window::Window;
+use crate::widgets::{json, toml, Widget};
+
+/// Место в исходном тексте описания, строки и столбцы с единицы.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Ошибка разбора или построения дерева виджетов.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadError {
+    pub message: String,
+    pub position: Position,
+}
+
+impl LoadError {
+    pub fn new(message: impl Into<String>, position: Position) -> LoadError {
+        LoadError { message: message.into(), position }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "строка {}, столбец {}: {}", self.position.line, self.position.column, self.message)
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Ошибка сохранения: виджет, который не умеет себя описать.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveError {
+    pub widget: Accessible,
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "не сохраняется: {}", self.widget.role.name())?;
+        if !self.widget.name.is_empty() {
+            write!(f, " «{}»", self.widget.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Ключ таблицы вместе с местом, где он записан.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+    pub name: String,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+    Table(Vec<(Key, Value)>),
+}
+
+/// Значение документа — общее для TOML и JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Value {
+    pub kind: Kind,
+    pub position: Position,
+}
+
+impl Value {
+    pub fn new(kind: Kind, position: Position) -> Value {
+        Value { kind, position }
+    }
+
+    /// Таблица из пар «имя — значение», без позиций (для сохранения).
+    pub fn table(entries: Vec<(&str, Value)>) -> Value {
+        let entries = entries
+            .into_iter()
+            .map(|(name, value)| (Key { name: name.to_owned(), position: Position::default() }, value))
+            .collect();
+        Value::from(Kind::Table(entries))
+    }
+
+    /// Добавляет ключ в таблицу; у значений других видов ничего не делает.
+    pub fn set(&mut self, name: &str, value: Value) {
+        if let Kind::Table(entries) = &mut self.kind {
+            entries.push((Key { name: name.to_owned(), position: Position::default() }, value));
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match &self.kind {
+            Kind::Table(entries) => entries.iter().find(|(key, _)| key.name == name).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self.kind {
+            Kind::String(_) => "строка",
+            Kind::Integer(_) => "число",
+            Kind::Boolean(_) => "логическое значение",
+            Kind::Array(_) => "массив",
+            Kind::Table(_) => "таблица",
+        }
+    }
+}
+
+impl From<Kind> for Value {
+    fn from(kind: Kind) -> Value {
+        Value::new(kind, Position::default())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::from(Kind::String(value.to_owned()))
+    }
+}
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Value {
+        Value::from(Kind::Integer(value as i64))
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Value {
+        Value::from(Kind::Boolean(value))
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(values: Vec<Value>) -> Value {
+        Value::from(Kind::Array(values))
+    }
+}
+
+/// Посимвольное чтение текста с подсчётом строк и столбцов — общее для разборщиков.
+pub struct Reader {
+    chars: Vec<char>,
+    index: usize,
+    position: Position,
+}
+
+impl Reader {
+    pub fn new(text: &str) -> Reader {
+        Reader { chars: text.chars().collect(), index: 0, position: Position { line: 1, column: 1 } }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    /// Ещё не прочитанные символы.
+    pub fn rest(&self) -> &[char] {
+        &self.chars[self.index..]
+    }
+
+    /// Следующие символы совпадают с `text`.
+    pub fn looking_at(&self, text: &str) -> bool {
+        text.chars().enumerate().all(|(i, c)| self.chars.get(self.index + i) == Some(&c))
+    }
+
+    pub fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+        if c == '\n' {
+            self.position = Position { line: self.position.line + 1, column: 1 };
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Пропускает `c`, если он следующий.
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect(&mut self, c: char) -> Result<(), LoadError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", c)))
+        }
+    }
+
+    pub fn skip_while(&mut self, f: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&f) {
+            self.next();
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> LoadError {
+        LoadError::new(message, self.position)
+    }
+
+    /// Ошибка «ожидалось …» с описанием того, что встретилось на самом деле.
+    pub fn unexpected(&self, expected: &str) -> LoadError {
+        match self.peek() {
+            Some(c) if c.is_control() => {
+                self.error(format!("ожидалось {}, а встретилось `{}`", expected, c.escape_debug()))
+            }
+            Some(c) => self.error(format!("ожидалось {}, а встретилось `{}`", expected, c)),
+            None => self.error(format!("ожидалось {}, а текст закончился", expected)),
+        }
+    }
+
+    /// Код символа из `count` шестнадцатеричных цифр (для `\uXXXX`).
+    pub fn hex_escape(&mut self, count: usize) -> Result<u32, LoadError> {
+        let mut code = 0;
+        for _ in 0..count {
+            let digit = self.peek().and_then(|c| c.to_digit(16)).ok_or_else(|| self.unexpected("шестнадцатеричная цифра"))?;
+            self.next();
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+}
+
+/// Строит дерево виджетов из TOML-описания.
+pub fn load_toml(text: &str) -> Result<Box<dyn Widget>, LoadError> {
+    build(&toml::parse(text)?)
+}
+
+/// Строит дерево виджетов из JSON-описания.
+pub fn load_json(text: &str) -> Result<Box<dyn Widget>, LoadError> {
+    build(&json::parse(text)?)
+}
+
+/// Записывает дерево в TOML. Описываются окна, надписи, кнопки, контейнеры и элементы форм
+/// (флажки, переключатели, поля ввода, таблицы); списки, меню, вкладки, индикаторы
+/// и диаграммы держат данные в замыканиях или моделях и не сохраняются.
+pub fn to_toml(widget: &dyn Widget) -> Result<String, SaveError> {
+    describe(widget).map(|value| toml::write(&value))
+}
+
+pub fn to_json(widget: &dyn Widget) -> Result<String, SaveError> {
+    describe(widget).map(|value| json::write(&value))
+}
+
+fn describe(widget: &dyn Widget) -> Result<Value, SaveError> {
+    widget.describe().ok_or_else(|| SaveError { widget: undescribed(widget).accessible() })
+}
+
+/// Самый глубокий виджет без описания: контейнер не описывается, если не описывается его потомок.
+fn undescribed(widget: &dyn Widget) -> &dyn Widget {
+    match widget.children().into_iter().find(|child| child.describe().is_none()) {
+        Some(child) => undescribed(child),
+        None => widget,
+    }
+}
+
+/// Свойства одного виджета: проверяет типы и отвергает незнакомые ключи.
+struct Properties<'a> {
+    kind: &'a str,
+    value: &'a Value,
+}
+
+impl<'a> Properties<'a> {
+    fn check(&self, allowed: &[&str]) -> Result<(), LoadError> {
+        let Kind::Table(entries) = &self.value.kind else {
+            return Ok(());
+        };
+        match entries.iter().find(|(key, _)| key.name != "type" && !allowed.contains(&key.name.as_str())) {
+            Some((key, _)) => Err(LoadError::new(
+                format!("у виджета `{}` нет свойства `{}`", self.kind, key.name),
+                key.position,
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn mismatch(&self, name: &str, expected: &str, value: &Value) -> LoadError {
+        LoadError::new(
+            format!("свойство `{}` должно быть {}, а не {}", name, expected, value.type_name()),
+            value.position,
+        )
+    }
+
+    fn string(&self, name: &str) -> Result<Option<&'a str>, LoadError> {
+        match self.value.get(name) {
+            None => Ok(None),
+            Some(Value { kind: Kind::String(text), .. }) => Ok(Some(text)),
+            Some(value) => Err(self.mismatch(name, "строкой", value)),
+        }
+    }
+
+    fn number(&self, name: &str) -> Result<Option<usize>, LoadError> {
+        match self.value.get(name) {
+            None => Ok(None),
+            Some(Value { kind: Kind::Integer(n), position }) => {
+                usize::try_from(*n).map(Some).map_err(|_| LoadError::new(format!("`{}` не может быть отрицательным", name), *position))
+            }
+            Some(value) => Err(self.mismatch(name, "числом", value)),
+        }
+    }
+
+    /// Обязательное числовое свойство.
+    fn required_number(&self, name: &str) -> Result<usize, LoadError> {
+        let missing = || LoadError::new(format!("у виджета `{}` не указано `{}`", self.kind, name), self.value.position);
+        self.number(name)?.ok_or_else(missing)
+    }
+
+    fn boolean(&self, name: &str) -> Result<Option<bool>, LoadError> {
+        match self.value.get(name) {
+            None => Ok(None),
+            Some(Value { kind: Kind::Boolean(flag), .. }) => Ok(Some(*flag)),
+            Some(value) => Err(self.mismatch(name, "true или false", value)),
+        }
+    }
+
+    /// Строковое свойство из фиксированного набора вариантов.
+    fn choice<T: Copy>(&self, name: &str, options: &[(&str, T)]) -> Result<Option<T>, LoadError> {
+        let Some(text) = self.string(name)? else {
+            return Ok(None);
+        };
+        match options.iter().find(|(option, _)| *option == text) {
+            Some((_, value)) => Ok(Some(*value)),
+            None => {
+                let names: Vec<&str> = options.iter().map(|(option, _)| *option).collect();
+                let position = self.value.get(name).map(|value| value.position).unwrap_or_default();
+                Err(LoadError::new(format!("`{}` не подходит для `{}`, варианты: {}", text, name, names.join(", ")), position))
+            }
+        }
+    }
+
+    fn array(&self, name: &str) -> Result<&'a [Value], LoadError> {
+        match self.value.get(name) {
+            None => Ok(&[]),
+            Some(Value { kind: Kind::Array(items), .. }) => Ok(items),
+            Some(value) => Err(self.mismatch(name, "массивом", value)),
+        }
+    }
+
+    fn strings(&self, name: &str) -> Result<Vec<&'a str>, LoadError> {
+        self.array(name)?
+            .iter()
+            .map(|item| match &item.kind {
+                Kind::String(text) => Ok(text.as_str()),
+                _ => Err(self.mismatch(name, "массивом строк", item)),
+            })
+            .collect()
+    }
+
+    fn children(&self) -> Result<&'a [Value], LoadError> {
+        self.array("children")
+    }
+
+    fn border(&self) -> Result<Option<BorderStyle>, LoadError> {
+        self.choice("border", &BorderStyle::PRESETS)
+    }
+}
+
+const ALIGNS: &[(&str, Align)] =
+    &[("left", Align::Left), ("center", Align::Center), ("right", Align::Right), ("justify", Align::Justify)];
+
+const WRAPS: &[(&str, Wrap)] = &[("none", Wrap::None), ("char", Wrap::Char), ("word", Wrap::Word)];
+
+const CROSS_ALIGNS: &[(&str, CrossAlign)] = &[
+    ("start", CrossAlign::Start),
+    ("center", CrossAlign::Center),
+    ("end", CrossAlign::End),
+    ("stretch", CrossAlign::Stretch),
+];
+
+/// Имя варианта для сохранения (обратное к разбору).
+pub fn choice_name<T: PartialEq>(options: &[(&'static str, T)], value: &T) -> &'static str {
+    options.iter().find(|(_, option)| option == value).map(|(name, _)| *name).unwrap_or_default()
+}
+
+pub fn align_name(align: Align) -> &'static str {
+    choice_name(ALIGNS, &align)
+}
+
+pub fn wrap_name(wrap: Wrap) -> &'static str {
+    choice_name(WRAPS, &wrap)
+}
+
+pub fn cross_align_name(align: CrossAlign) -> &'static str {
+    choice_name(CROSS_ALIGNS, &align)
+}
+
+/// Строит виджет по описанию: таблица с ключом `type` и свойствами виджета.
+pub fn build(value: &Value) -> Result<Box<dyn Widget>, LoadError> {
+    build_child(value, false).map(|(widget, _)| widget)
+}
+
+/// Виджет и его вес `flex`, который разрешён только внутри `hbox`/`vbox`.
+fn build_child(value: &Value, in_box: bool) -> Result<(Box<dyn Widget>, usize), LoadError> {
+    if !matches!(value.kind, Kind::Table(_)) {
+        return Err(LoadError::new(format!("описание виджета должно быть таблицей, а не {}", value.type_name()), value.position));
+    }
+    let kind = match value.get("type") {
+        Some(Value { kind: Kind::String(kind), .. }) => kind.as_str(),
+        Some(other) => return Err(LoadError::new("`type` должен быть строкой", other.position)),
+        None => return Err(LoadError::new("у виджета не указан `type`", value.position)),
+    };
+    let props = Properties { kind, value };
+    let extra: &[&str] = if in_box { &["flex"] } else { &[] };
+    let allowed = |names: &[&'static str]| [names, extra].concat();
+
+    let widget: Box<dyn Widget> = match kind {
+        "window" => {
+            props.check(&allowed(&["title", "width", "height", "border", "children"]))?;
+            let mut window = Window::new(props.string("title")?.unwrap_or_default());
+            match (props.number("width")?, props.number("height")?) {
+                (Some(width), Some(height)) => window.set_size(width, height),
+                (None, None) => {}
+                _ => return Err(LoadError::new("`width` и `height` окна задаются вместе", value.position)),
+            }
+            if let Some(border) = props.border()? {
+                window.set_border(border);
+            }
+            for child in props.children()? {
+                window.add_widget(build(child)?);
+            }
+            Box::new(window)
+        }
+        "label" => {
+            props.check(&allowed(&["text", "wrap", "align", "ellipsis", "max_width"]))?;
+            let mut label = Label::new(props.string("text")?.unwrap_or_default());
+            if let Some(wrap) = props.choice("wrap", WRAPS)? {
+                label.set_wrap(wrap);
+            }
+            if let Some(align) = props.choice("align", ALIGNS)? {
+                label.set_align(align);
+            }
+            label.set_ellipsis(props.boolean("ellipsis")?.unwrap_or(false));
+            label.set_max_width(props.number("max_width")?);
+            Box::new(label)
+        }
+        "button" => {
+            props.check(&allowed(&["label", "border"]))?;
+            let mut button = Button::new(props.string("label")?.unwrap_or_default());
+            if let Some(border) = props.border()? {
+                button.set_border(border);
+            }
+            Box::new(button)
+        }
+        "hbox" | "vbox" => {
+            props.check(&allowed(&["spacing", "padding", "align", "breakpoint", "children"]))?;
+            let spacing = props.number("spacing")?.unwrap_or(0);
+            let breakpoint = props.number("breakpoint")?;
+            let padding = props.number("padding")?.unwrap_or(0);
+            let align = props.choice("align", CROSS_ALIGNS)?;
+            let mut children = Vec::new();
+            for child in props.children()? {
+                children.push(build_child(child, true)?);
+            }
+            macro_rules! fill {
+                ($container:expr) => {{
+                    let mut container = $container;
+                    container.set_spacing(spacing);
+                    container.set_padding(padding);
+                    container.set_breakpoint(breakpoint);
+                    if let Some(align) = align {
+                        container.set_align(align);
+                    }
+                    for (child, flex) in children {
+                        container.add_flex(child, flex);
+                    }
+                    Box::new(container) as Box<dyn Widget>
+                }};
+            }
+            if kind == "hbox" {
+                fill!(HBox::new())
+            } else {
+                fill!(VBox::new())
+            }
+        }
+        "checkbox" => {
+            props.check(&allowed(&["label", "checked"]))?;
+            let mut checkbox = Checkbox::new(props.string("label")?.unwrap_or_default());
+            checkbox.set_checked(props.boolean("checked")?.unwrap_or(false));
+            Box::new(checkbox)
+        }
+        "toggle" => {
+            props.check(&allowed(&["label", "on", "on_text", "off_text"]))?;
+            let mut toggle = Toggle::new(props.string("label")?.unwrap_or_default());
+            toggle.set_on(props.boolean("on")?.unwrap_or(false));
+            toggle.set_texts(props.string("on_text")?.unwrap_or("ON"), props.string("off_text")?.unwrap_or("OFF"));
+            Box::new(toggle)
+        }
+        "radio_group" => {
+            props.check(&allowed(&["title", "options", "selected", "border"]))?;
+            let mut group = RadioGroup::new(props.string("title")?.unwrap_or_default(), &props.strings("options")?);
+            if let Some(selected) = props.number("selected")? {
+                if !group.select(selected) {
+                    let position = value.get("selected").map(|selected| selected.position).unwrap_or_default();
+                    return Err(LoadError::new("`selected` указывает за последний вариант", position));
+                }
+            }
+            if let Some(border) = props.border()? {
+                group.set_border(border);
+            }
+            Box::new(group)
+        }
+        "text_field" => {
+            props.check(&allowed(&["width", "text", "placeholder", "max_length", "password"]))?;
+            let mut field = TextField::new(props.required_number("width")?);
+            field.set_max_length(props.number("max_length")?);
+            field.set_text(props.string("text")?.unwrap_or_default());
+            field.set_placeholder(props.string("placeholder")?.unwrap_or_default());
+            field.set_password(props.boolean("password")?.unwrap_or(false));
+            Box::new(field)
+        }
+        "text_area" => {
+            props.check(&allowed(&["width", "height", "text", "placeholder", "max_length"]))?;
+            let mut area = TextArea::new(props.required_number("width")?, props.required_number("height")?);
+            area.set_max_length(props.number("max_length")?);
+            area.set_text(props.string("text")?.unwrap_or_default());
+            area.set_placeholder(props.string("placeholder")?.unwrap_or_default());
+            Box::new(area)
+        }
+        "table" => {
+            props.check(&allowed(&["columns", "rows", "header_rule", "row_separators", "border"]))?;
+            let columns = props.array("columns")?;
+            let mut headers = Vec::new();
+            for column in columns {
+                let column = Properties { kind: "column", value: column };
+                column.check(&["title", "width", "percent", "align"])?;
+                headers.push(column.string("title")?.unwrap_or_default());
+            }
+            let mut table = Table::new(&headers);
+            for (index, column) in columns.iter().enumerate() {
+                let props = Properties { kind: "column", value: column };
+                match (props.number("width")?, props.number("percent")?) {
+                    (Some(width), None) => table.set_column_width(index, ColumnWidth::Fixed(width)),
+                    (None, Some(percent)) => table.set_column_width(index, ColumnWidth::Percent(percent)),
+                    (None, None) => {}
+                    _ => {
+                        return Err(LoadError::new("у столбца задаётся либо `width`, либо `percent`", column.position));
+                    }
+                }
+                if let Some(align) = props.choice("align", ALIGNS)? {
+                    table.set_column_align(index, align);
+                }
+            }
+            for row in props.array("rows")? {
+                let Kind::Array(cells) = &row.kind else {
+                    return Err(props.mismatch("rows", "массивом строк таблицы", row));
+                };
+                table.add_row(cells.iter().map(build).collect::<Result<_, _>>()?);
+            }
+            table.set_header_rule(props.boolean("header_rule")?.unwrap_or(true));
+            table.set_row_separators(props.boolean("row_separators")?.unwrap_or(false));
+            if let Some(border) = props.border()? {
+                table.set_border(border);
+            }
+            Box::new(table)
+        }
+        _ => {
+            let position = value.get("type").map(|kind| kind.position).unwrap_or_default();
+            return Err(LoadError::new(format!("неизвестный тип виджета `{}`", kind), position));
+        }
+    };
+    let flex = if in_box { props.number("flex")?.unwrap_or(0) } else { 0 };
+    Ok((widget, flex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render;
+
+    const SCREEN: &str = r#"
+type = "window"
+title = "Вход"
+
+[[children]]
+type = "label"
+text = "Введите имя и пароль"
+
+[[children]]
+type = "hbox"
+spacing = 1
+
+[[children.children]]
+type = "button"
+label = "OK"
+
+[[children.children]]
+type = "button"
+label = "Отмена"
+border = "rounded"
+"#;
+
+    #[test]
+    fn toml_and_json_build_the_same_tree() {
+        let from_toml = load_toml(SCREEN).unwrap();
+        let json = to_json(from_toml.as_ref()).unwrap();
+        let from_json = load_json(&json).unwrap();
+        assert_eq!(render(from_toml.as_ref()), render(from_json.as_ref()));
+        assert!(render(from_toml.as_ref()).contains("╭"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let window = load_toml(SCREEN).unwrap();
+        let written = to_toml(window.as_ref()).unwrap();
+        assert_eq!(to_toml(load_toml(&written).unwrap().as_ref()).unwrap(), written);
+        let json = to_json(window.as_ref()).unwrap();
+        assert_eq!(to_json(load_json(&json).unwrap().as_ref()).unwrap(), json);
+    }
+
+    const FORM: &str = r#"
+type = "vbox"
+
+[[children]]
+type = "checkbox"
+label = "Запомнить меня"
+checked = true
+
+[[children]]
+type = "toggle"
+label = "Звук"
+on_text = "да"
+off_text = "нет"
+
+[[children]]
+type = "radio_group"
+title = "Цвет"
+options = ["красный", "зелёный"]
+selected = 1
+border = "rounded"
+
+[[children]]
+type = "text_field"
+width = 12
+text = "секрет"
+max_length = 8
+password = true
+
+[[children]]
+type = "text_area"
+width = 10
+height = 2
+placeholder = "Заметки"
+
+[[children]]
+type = "table"
+rows = [[{ type = "label", text = "Анна" }, { type = "checkbox", label = "да" }]]
+header_rule = false
+
+[[children.columns]]
+title = "Имя"
+width = 6
+
+[[children.columns]]
+title = "Гость"
+align = "right"
+"#;
+
+    #[test]
+    fn form_controls_round_trip() {
+        let form = load_toml(FORM).unwrap();
+        assert_eq!(to_toml(form.as_ref()).unwrap(), FORM.trim_start());
+        let json = to_json(form.as_ref()).unwrap();
+        assert_eq!(render(load_json(&json).unwrap().as_ref()), render(form.as_ref()));
+        assert!(render(form.as_ref()).contains("******"));
+    }
+
+    #[test]
+    fn save_errors_name_the_widget() {
+        let mut window = Window::new("Список");
+        window.add_widget(Box::new(Label::new("Файлы:")));
+        window.add_widget(Box::new(crate::widgets::ListView::new(vec!["a.txt"], 8, 2)));
+        let error = to_toml(&window).unwrap_err();
+        assert_eq!(error.widget.role, crate::widgets::AccessRole::List);
+        assert_eq!(error.to_string(), "не сохраняется: список");
+    }
+
+    #[test]
+    fn form_control_errors() {
+        let error = load_toml("type = \"text_field\"\n").err().unwrap();
+        assert_eq!(error.message, "у виджета `text_field` не указано `width`");
+        let error = load_toml("type = \"radio_group\"\noptions = [\"а\"]\nselected = 1\n").err().unwrap();
+        assert_eq!(error.position, Position { line: 3, column: 12 });
+        let table = "{\"type\": \"table\", \"columns\": [{\"title\": \"A\", \"width\": 2, \"percent\": 50}]}";
+        let error = load_json(table).err().unwrap();
+        assert_eq!(error.message, "у столбца задаётся либо `width`, либо `percent`");
+    }
+
+    #[test]
+    fn errors_point_at_the_source() {
+        let error = load_toml("type = \"window\"\n[[children]]\ntype = \"slider\"\n").err().unwrap();
+        assert_eq!(error.position, Position { line: 3, column: 8 });
+        assert_eq!(error.to_string(), "строка 3, столбец 8: неизвестный тип виджета `slider`");
+
+        let error = load_json("{\"type\": \"label\",\n  \"colour\": \"red\"}").err().unwrap();
+        assert_eq!(error.position, Position { line: 2, column: 3 });
+
+        let error = load_json("{\"type\": \"window\", \"width\": \"wide\"}").err().unwrap();
+        assert_eq!(error.message, "свойство `width` должно быть числом, а не строка");
+    }
+}
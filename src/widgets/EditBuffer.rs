@@ -0,0 +1,396 @@
+use crate::widgets::canvas::Canvas;
+use crate::widgets::event::{KeyCode, KeyEvent};
+use crate::widgets::layout::Rect;
+use crate::widgets::style::Style;
+use crate::widgets::unicode::{grapheme_width, graphemes};
+
+/// Проверка введённого текста: `Err` с сообщением делает поле недействительным.
+pub type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// Обработчик изменения текста; получает новый текст.
+pub type ChangeHandler = Box<dyn FnMut(&str)>;
+
+/// Редактируемый текст с курсором и выделением — общая часть `TextField` и `TextArea`.
+/// Позиции хранятся в байтах и всегда стоят на границах графемных кластеров.
+#[derive(Debug, Clone, Default)]
+pub struct EditBuffer {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+    max_length: Option<usize>,
+    multiline: bool,
+}
+
+fn is_word(grapheme: &str) -> bool {
+    grapheme.chars().next().is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+impl EditBuffer {
+    pub fn new(multiline: bool) -> EditBuffer {
+        EditBuffer { multiline, ..EditBuffer::default() }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = if self.multiline { text.to_owned() } else { text.replace('\n', " ") };
+        if let Some(max) = self.max_length {
+            self.text = graphemes(&self.text).take(max).collect();
+        }
+        self.cursor = self.text.len();
+        self.anchor = None;
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Ограничение длины в графемах.
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+        let text = self.text.clone();
+        self.set_text(&text);
+    }
+
+    /// Выделенный диапазон байтов, если выделение не пустое.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.anchor?;
+        (anchor != self.cursor).then(|| (anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|(start, end)| &self.text[start..end])
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+    }
+
+    fn prev_boundary(&self, position: usize) -> usize {
+        graphemes(&self.text[..position]).last().map_or(0, |g| position - g.len())
+    }
+
+    fn next_boundary(&self, position: usize) -> usize {
+        graphemes(&self.text[position..]).next().map_or(position, |g| position + g.len())
+    }
+
+    /// Начало предыдущего слова: пропускаем разделители, затем символы слова.
+    fn prev_word(&self, mut position: usize) -> usize {
+        while position > 0 && !is_word(graphemes(&self.text[..position]).last().unwrap_or("")) {
+            position = self.prev_boundary(position);
+        }
+        while position > 0 && is_word(graphemes(&self.text[..position]).last().unwrap_or("")) {
+            position = self.prev_boundary(position);
+        }
+        position
+    }
+
+    fn next_word(&self, mut position: usize) -> usize {
+        let at = |position: usize| graphemes(&self.text[position..]).next().unwrap_or("");
+        while position < self.text.len() && !is_word(at(position)) {
+            position = self.next_boundary(position);
+        }
+        while position < self.text.len() && is_word(at(position)) {
+            position = self.next_boundary(position);
+        }
+        position
+    }
+
+    fn line_start(&self, position: usize) -> usize {
+        self.text[..position].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self, position: usize) -> usize {
+        self.text[position..].find('\n').map_or(self.text.len(), |i| position + i)
+    }
+
+    /// Номер строки и столбец (в столбцах терминала) для позиции.
+    pub fn line_col(&self, position: usize) -> (usize, usize) {
+        let line = self.text[..position].matches('\n').count();
+        let start = self.line_start(position);
+        (line, graphemes(&self.text[start..position]).map(grapheme_width).sum())
+    }
+
+    /// Позиция в строке `start..`, ближайшая к столбцу `column`.
+    fn at_column(&self, start: usize, column: usize) -> usize {
+        let end = self.line_end(start);
+        let mut position = start;
+        let mut used = 0;
+        for grapheme in graphemes(&self.text[start..end]) {
+            used += grapheme_width(grapheme);
+            if used > column {
+                break;
+            }
+            position += grapheme.len();
+        }
+        position
+    }
+
+    fn move_to(&mut self, position: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = position;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.selection() else {
+            return false;
+        };
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+        self.anchor = None;
+        true
+    }
+
+    /// Вставляет текст вместо выделения; лишнее сверх `max_length` отбрасывается.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let text = if self.multiline { text.to_owned() } else { text.replace('\n', " ") };
+        let room = self.max_length.map_or(usize::MAX, |max| max.saturating_sub(graphemes(&self.text).count()));
+        let text: String = graphemes(&text).take(room).collect();
+        self.text.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+    }
+
+    pub fn backspace(&mut self) {
+        if !self.delete_selection() && self.cursor > 0 {
+            let start = self.prev_boundary(self.cursor);
+            self.text.replace_range(start..self.cursor, "");
+            self.cursor = start;
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if !self.delete_selection() && self.cursor < self.text.len() {
+            let end = self.next_boundary(self.cursor);
+            self.text.replace_range(self.cursor..end, "");
+        }
+    }
+
+    pub fn delete_word_back(&mut self) {
+        if !self.delete_selection() {
+            let start = self.prev_word(self.cursor);
+            self.text.replace_range(start..self.cursor, "");
+            self.cursor = start;
+        }
+    }
+
+    /// Рисует текст в области, прокручивая его так, чтобы курсор был виден.
+    /// `mask` заменяет каждую графему (поле пароля), выделение и курсор показываются инверсией.
+    pub fn render(&self, canvas: &mut Canvas, area: Rect, style: Style, mask: Option<char>, show_cursor: bool) {
+        let inverted = Style { reverse: !style.reverse, ..style };
+        let mask = mask.map(|c| c.to_string());
+        let symbol = |grapheme: &'_ str| -> (String, usize) {
+            match &mask {
+                Some(mask) => (mask.clone(), grapheme_width(mask)),
+                None => (grapheme.to_owned(), grapheme_width(grapheme)),
+            }
+        };
+
+        // Столбец курсора считается по тем же символам, что выводятся на экран.
+        let cursor_line = self.text[..self.cursor].matches('\n').count();
+        let line_start = self.line_start(self.cursor);
+        let cursor_column: usize = graphemes(&self.text[line_start..self.cursor]).map(|g| symbol(g).1).sum();
+        let top = cursor_line.saturating_sub(area.height.saturating_sub(1));
+        let left = cursor_column.saturating_sub(area.width.saturating_sub(1));
+        let selection = self.selection();
+
+        canvas.fill(area, " ", style);
+        let mut offset = 0;
+        for (row, line) in self.text.split('\n').enumerate() {
+            let line_offset = offset;
+            offset += line.len() + 1;
+            if row < top || row - top >= area.height {
+                continue;
+            }
+            let y = area.y + row - top;
+            let mut column = 0;
+            let mut position = line_offset;
+            for grapheme in graphemes(line) {
+                let (text, width) = symbol(grapheme);
+                let selected = selection.is_some_and(|(start, end)| position >= start && position < end);
+                if column >= left && column + width - left <= area.width {
+                    canvas.set(area.x + column - left, y, &text, if selected { inverted } else { style });
+                }
+                column += width;
+                position += grapheme.len();
+            }
+        }
+        if show_cursor && cursor_column - left < area.width && cursor_line - top < area.height {
+            let caret = Rect::new(area.x + cursor_column - left, area.y + cursor_line - top, 1, 1);
+            canvas.set_style(caret, inverted);
+        }
+    }
+
+    /// Применяет клавишу редактирования. Возвращает `Some(changed)`, если клавиша
+    /// обработана (`changed` — изменился ли текст), и `None`, если нет.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<bool> {
+        let select = key.modifiers.shift;
+        let ctrl = key.modifiers.ctrl;
+        let old = self.text.clone();
+
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.select_all(),
+            KeyCode::Char('w') if ctrl => self.delete_word_back(),
+            KeyCode::Char(c) if !ctrl && !key.modifiers.alt => self.insert(&c.to_string()),
+            KeyCode::Enter if self.multiline => self.insert("\n"),
+            KeyCode::Backspace if ctrl || key.modifiers.alt => self.delete_word_back(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left if ctrl => self.move_to(self.prev_word(self.cursor), select),
+            KeyCode::Right if ctrl => self.move_to(self.next_word(self.cursor), select),
+            KeyCode::Left => match self.selection() {
+                Some((start, _)) if !select => self.move_to(start, false),
+                _ => self.move_to(self.prev_boundary(self.cursor), select),
+            },
+            KeyCode::Right => match self.selection() {
+                Some((_, end)) if !select => self.move_to(end, false),
+                _ => self.move_to(self.next_boundary(self.cursor), select),
+            },
+            KeyCode::Home if ctrl => self.move_to(0, select),
+            KeyCode::End if ctrl => self.move_to(self.text.len(), select),
+            KeyCode::Home => self.move_to(self.line_start(self.cursor), select),
+            KeyCode::End => self.move_to(self.line_end(self.cursor), select),
+            KeyCode::Up | KeyCode::Down if self.multiline => {
+                let (line, column) = self.line_col(self.cursor);
+                let start = self.line_start(self.cursor);
+                let target = if key.code == KeyCode::Up {
+                    if line == 0 {
+                        return Some(false);
+                    }
+                    self.line_start(start - 1)
+                } else {
+                    let end = self.line_end(self.cursor);
+                    if end == self.text.len() {
+                        return Some(false);
+                    }
+                    end + 1
+                };
+                self.move_to(self.at_column(target, column), select);
+            }
+            _ => return None,
+        }
+        Some(self.text != old)
+    }
+}
+
+/// Проверка текста и обработчик его изменения — вторая общая часть полей ввода,
+/// отдельно от `EditBuffer`, потому что замыкания не клонируются.
+#[derive(Default)]
+pub struct EditHooks {
+    validator: Option<Validator>,
+    error: Option<String>,
+    on_change: Option<ChangeHandler>,
+}
+
+impl EditHooks {
+    /// Ставит проверку и сразу проверяет текущий текст.
+    pub fn set_validator(&mut self, validator: impl Fn(&str) -> Result<(), String> + 'static, text: &str) {
+        self.validator = Some(Box::new(validator));
+        self.validate(text);
+    }
+
+    pub fn set_on_change(&mut self, on_change: impl FnMut(&str) + 'static) {
+        self.on_change = Some(Box::new(on_change));
+    }
+
+    /// Сообщение последней неудачной проверки.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn validate(&mut self, text: &str) {
+        self.error = self.validator.as_ref().and_then(|validator| validator(text).err());
+    }
+
+    /// Передаёт клавишу буферу; изменённый текст проверяется и уходит обработчику.
+    /// Возвращает, обработана ли клавиша.
+    pub fn handle_key(&mut self, buffer: &mut EditBuffer, key: &KeyEvent) -> bool {
+        match buffer.handle_key(key) {
+            Some(true) => {
+                self.validate(buffer.text());
+                if let Some(on_change) = &mut self.on_change {
+                    on_change(buffer.text());
+                }
+                true
+            }
+            Some(false) => true,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(buffer: &mut EditBuffer, keys: &[KeyEvent]) {
+        for key in keys {
+            buffer.handle_key(key);
+        }
+    }
+
+    #[test]
+    fn insert_and_delete() {
+        let mut buffer = EditBuffer::new(false);
+        buffer.insert("привет");
+        buffer.backspace();
+        assert_eq!(buffer.text(), "приве");
+        keys(&mut buffer, &[KeyEvent::new(KeyCode::Home), KeyEvent::new(KeyCode::Delete)]);
+        assert_eq!(buffer.text(), "риве");
+        buffer.insert("e\u{301}");
+        buffer.backspace();
+        assert_eq!(buffer.text(), "риве");
+    }
+
+    #[test]
+    fn word_movement_and_selection() {
+        let mut buffer = EditBuffer::new(false);
+        buffer.insert("one two  three");
+        keys(&mut buffer, &[KeyEvent::ctrl(KeyCode::Left), KeyEvent::ctrl(KeyCode::Left)]);
+        assert_eq!(buffer.cursor(), 4);
+        let select_word = KeyEvent {
+            code: KeyCode::Right,
+            modifiers: crate::widgets::event::Modifiers { ctrl: true, shift: true, alt: false },
+        };
+        buffer.handle_key(&select_word);
+        assert_eq!(buffer.selected_text(), Some("two"));
+        buffer.insert("2");
+        assert_eq!(buffer.text(), "one 2  three");
+        buffer.delete_word_back();
+        assert_eq!(buffer.text(), "one   three");
+    }
+
+    #[test]
+    fn max_length_counts_graphemes() {
+        let mut buffer = EditBuffer::new(false);
+        buffer.set_max_length(Some(3));
+        buffer.insert("日本語テキスト");
+        assert_eq!(buffer.text(), "日本語");
+        buffer.insert("x");
+        assert_eq!(buffer.text(), "日本語");
+    }
+
+    #[test]
+    fn multiline_up_down_keep_column() {
+        let mut buffer = EditBuffer::new(true);
+        buffer.insert("abcd\nx\nabcd");
+        keys(&mut buffer, &[KeyEvent::new(KeyCode::Left), KeyEvent::new(KeyCode::Up)]);
+        assert_eq!(buffer.line_col(buffer.cursor()), (1, 1));
+        buffer.handle_key(&KeyEvent::new(KeyCode::Up));
+        assert_eq!(buffer.line_col(buffer.cursor()), (0, 1));
+        assert_eq!(buffer.handle_key(&KeyEvent::new(KeyCode::Enter)), Some(true));
+        assert_eq!(buffer.text(), "a\nbcd\nx\nabcd");
+        assert_eq!(buffer.handle_key(&KeyEvent::new(KeyCode::Up)), Some(false));
+    }
+}
@@ -0,0 +1,363 @@
+use crate::widgets::layout::Size;
+
+/// Клавиша без учёта модификаторов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCode {
+    Char(char),
+    Enter,
+    Tab,
+    BackTab,
+    Backspace,
+    Delete,
+    Insert,
+    Esc,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub alt: bool,
+    pub ctrl: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub code: KeyCode,
+    pub modifiers: Modifiers,
+}
+
+impl KeyEvent {
+    pub fn new(code: KeyCode) -> KeyEvent {
+        KeyEvent { code, modifiers: Modifiers::default() }
+    }
+
+    pub fn ctrl(code: KeyCode) -> KeyEvent {
+        KeyEvent { code, modifiers: Modifiers { ctrl: true, ..Modifiers::default() } }
+    }
+
+    pub fn alt(code: KeyCode) -> KeyEvent {
+        KeyEvent { code, modifiers: Modifiers { alt: true, ..Modifiers::default() } }
+    }
+
+    pub fn shift(code: KeyCode) -> KeyEvent {
+        KeyEvent { code, modifiers: Modifiers { shift: true, ..Modifiers::default() } }
+    }
+
+    /// Клавиша нажата без модификаторов (Shift для печатных символов не считается).
+    pub fn is(&self, code: KeyCode) -> bool {
+        self.code == code && !self.modifiers.ctrl && !self.modifiers.alt
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseKind {
+    Press(MouseButton),
+    Release,
+    Drag(MouseButton),
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Событие мыши в координатах экрана (с нуля).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent {
+    pub kind: MouseKind,
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    /// Терминал сменил размер; новый размер в столбцах и строках.
+    Resize(Size),
+}
+
+impl Event {
+    pub fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code))
+    }
+
+    /// Последовательность нажатий для печати строки — удобно в сценариях тестов.
+    pub fn text(text: &str) -> Vec<Event> {
+        text.chars().map(|c| Event::key(KeyCode::Char(c))).collect()
+    }
+
+    pub fn click(x: usize, y: usize) -> Event {
+        Event::Mouse(MouseEvent { kind: MouseKind::Press(MouseButton::Left), x, y })
+    }
+}
+
+/// Модификаторы в параметрах CSI: 1 + shift + 2 * alt + 4 * ctrl.
+fn csi_modifiers(param: Option<&str>) -> Modifiers {
+    let bits = param.and_then(|p| p.parse::<u8>().ok()).unwrap_or(1).saturating_sub(1);
+    Modifiers { shift: bits & 1 != 0, alt: bits & 2 != 0, ctrl: bits & 4 != 0 }
+}
+
+/// Разбирает `ESC [ ...`; возвращает событие и длину последовательности.
+fn parse_csi(input: &str) -> Option<(Option<Event>, usize)> {
+    let body = input.strip_prefix("\x1b[")?;
+    let end = body.find(|c: char| ('\u{40}'..='\u{7E}').contains(&c))?;
+    let (params, last) = (&body[..end], body[end..].chars().next()?);
+    let length = 2 + end + 1;
+
+    if let Some(params) = params.strip_prefix('<') {
+        // SGR-мышь: ESC [ < кнопка ; x ; y (M — нажатие, m — отпускание)
+        let numbers: Vec<usize> = params.split(';').filter_map(|n| n.parse().ok()).collect();
+        let [button, x, y] = numbers[..] else {
+            return Some((None, length));
+        };
+        let pressed = |button: usize| match button & 3 {
+            0 => MouseButton::Left,
+            1 => MouseButton::Middle,
+            _ => MouseButton::Right,
+        };
+        let kind = match (button, last) {
+            (64, _) => MouseKind::ScrollUp,
+            (65, _) => MouseKind::ScrollDown,
+            (_, 'm') => MouseKind::Release,
+            (b, _) if b & 32 != 0 => MouseKind::Drag(pressed(b)),
+            (b, _) => MouseKind::Press(pressed(b)),
+        };
+        let event = MouseEvent { kind, x: x.saturating_sub(1), y: y.saturating_sub(1) };
+        return Some((Some(Event::Mouse(event)), length));
+    }
+
+    let mut parts = params.split(';');
+    let first = parts.next().filter(|p| !p.is_empty());
+    let modifiers = csi_modifiers(parts.next());
+    let code = match last {
+        'A' => KeyCode::Up,
+        'B' => KeyCode::Down,
+        'C' => KeyCode::Right,
+        'D' => KeyCode::Left,
+        'H' => KeyCode::Home,
+        'F' => KeyCode::End,
+        'Z' => KeyCode::BackTab,
+        '~' => match first {
+            Some("1") | Some("7") => KeyCode::Home,
+            Some("2") => KeyCode::Insert,
+            Some("3") => KeyCode::Delete,
+            Some("4") | Some("8") => KeyCode::End,
+            Some("5") => KeyCode::PageUp,
+            Some("6") => KeyCode::PageDown,
+            Some("15") => KeyCode::F(5),
+            Some("17") => KeyCode::F(6),
+            Some("18") => KeyCode::F(7),
+            Some("19") => KeyCode::F(8),
+            Some("20") => KeyCode::F(9),
+            Some("21") => KeyCode::F(10),
+            Some("23") => KeyCode::F(11),
+            Some("24") => KeyCode::F(12),
+            _ => return Some((None, length)),
+        },
+        _ => return Some((None, length)),
+    };
+    Some((Some(Event::Key(KeyEvent { code, modifiers })), length))
+}
+
+/// Превращает байты, прочитанные с терминала в сыром режиме, в события.
+/// Незнакомые последовательности пропускаются.
+pub fn parse_input(input: &str) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if let Some((event, length)) = parse_csi(rest) {
+            events.extend(event);
+            rest = &rest[length..];
+            continue;
+        }
+        if let Some(tail) = rest.strip_prefix("\x1bO") {
+            // SS3: F1–F4 и стрелки в режиме приложения
+            let code = match tail.chars().next() {
+                Some('P') => Some(KeyCode::F(1)),
+                Some('Q') => Some(KeyCode::F(2)),
+                Some('R') => Some(KeyCode::F(3)),
+                Some('S') => Some(KeyCode::F(4)),
+                Some('A') => Some(KeyCode::Up),
+                Some('B') => Some(KeyCode::Down),
+                Some('C') => Some(KeyCode::Right),
+                Some('D') => Some(KeyCode::Left),
+                _ => None,
+            };
+            if let Some(code) = code {
+                events.push(Event::key(code));
+                rest = &rest[3..];
+                continue;
+            }
+        }
+
+        let mut alt = false;
+        let mut c = c;
+        let mut length = c.len_utf8();
+        if c == '\x1b' {
+            match rest[1..].chars().next() {
+                Some(next) => {
+                    alt = true;
+                    c = next;
+                    length += next.len_utf8();
+                }
+                None => {
+                    events.push(Event::key(KeyCode::Esc));
+                    break;
+                }
+            }
+        }
+
+        let mut key = match c {
+            '\r' | '\n' => KeyEvent::new(KeyCode::Enter),
+            '\t' => KeyEvent::new(KeyCode::Tab),
+            '\x7f' | '\x08' => KeyEvent::new(KeyCode::Backspace),
+            '\x1b' => KeyEvent::new(KeyCode::Esc),
+            '\x01'..='\x1a' => KeyEvent::ctrl(KeyCode::Char((c as u8 - 1 + b'a') as char)),
+            c => KeyEvent::new(KeyCode::Char(c)),
+        };
+        key.modifiers.alt |= alt;
+        events.push(Event::Key(key));
+        rest = &rest[length..];
+    }
+    events
+}
+
+/// Собирает события из байтов, которые терминал отдаёт кусками. Многобайтный символ
+/// или `ESC [`/`ESC O` без завершающего байта, разрезанные между чтениями, ждут продолжения.
+/// Одиночный ESC в конце куска считается клавишей Esc, иначе её нажатие ждало бы следующего.
+#[derive(Debug, Default)]
+pub struct InputDecoder {
+    pending: Vec<u8>,
+}
+
+/// Дольше этого незавершённая последовательность не ждёт: это уже мусор, а не разрыв.
+const MAX_PENDING: usize = 32;
+
+impl InputDecoder {
+    pub fn new() -> InputDecoder {
+        InputDecoder::default()
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Event> {
+        self.pending.extend_from_slice(bytes);
+        let mut text = String::new();
+        let mut rest = &self.pending[..];
+        while let Err(error) = std::str::from_utf8(rest) {
+            let (valid, after) = rest.split_at(error.valid_up_to());
+            text.push_str(&String::from_utf8_lossy(valid));
+            match error.error_len() {
+                Some(length) => {
+                    text.push(char::REPLACEMENT_CHARACTER);
+                    rest = &after[length..];
+                }
+                // символ оборван на конце куска
+                None => {
+                    rest = after;
+                    break;
+                }
+            }
+        }
+        if let Ok(valid) = std::str::from_utf8(rest) {
+            text.push_str(valid);
+            rest = &[];
+        }
+        let mut tail = rest.to_vec();
+        if tail.is_empty() {
+            if let Some(start) = unfinished_escape(&text) {
+                tail = text.split_off(start).into_bytes();
+            }
+        }
+        if tail.len() > MAX_PENDING {
+            text.push_str(&String::from_utf8_lossy(&tail));
+            tail.clear();
+        }
+        self.pending = tail;
+        parse_input(&text)
+    }
+}
+
+/// Начало оборванной в конце текста последовательности `ESC [` или `ESC O`.
+fn unfinished_escape(text: &str) -> Option<usize> {
+    let start = text.rfind('\x1b')?;
+    let sequence = &text[start..];
+    let unfinished = match sequence.strip_prefix("\x1b[") {
+        Some(body) => !body.contains(|c: char| ('\u{40}'..='\u{7E}').contains(&c)),
+        None => sequence == "\x1bO",
+    };
+    unfinished.then_some(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_and_control_keys() {
+        assert_eq!(
+            parse_input("aЖ\r\t\x7f\x03"),
+            vec![
+                Event::key(KeyCode::Char('a')),
+                Event::key(KeyCode::Char('Ж')),
+                Event::key(KeyCode::Enter),
+                Event::key(KeyCode::Tab),
+                Event::key(KeyCode::Backspace),
+                Event::Key(KeyEvent::ctrl(KeyCode::Char('c'))),
+            ]
+        );
+    }
+
+    #[test]
+    fn escape_sequences() {
+        assert_eq!(
+            parse_input("\x1b[A\x1b[Z\x1b[1;5C\x1b[3~\x1bf\x1b"),
+            vec![
+                Event::key(KeyCode::Up),
+                Event::key(KeyCode::BackTab),
+                Event::Key(KeyEvent::ctrl(KeyCode::Right)),
+                Event::key(KeyCode::Delete),
+                Event::Key(KeyEvent::alt(KeyCode::Char('f'))),
+                Event::key(KeyCode::Esc),
+            ]
+        );
+    }
+
+    #[test]
+    fn sgr_mouse() {
+        assert_eq!(parse_input("\x1b[<0;5;3M"), vec![Event::click(4, 2)]);
+        assert_eq!(
+            parse_input("\x1b[<65;1;1M\x1b[<0;2;2m"),
+            vec![
+                Event::Mouse(MouseEvent { kind: MouseKind::ScrollDown, x: 0, y: 0 }),
+                Event::Mouse(MouseEvent { kind: MouseKind::Release, x: 1, y: 1 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn input_split_between_reads() {
+        let mut decoder = InputDecoder::new();
+        let bytes = "Жx\x1b[1;5C".as_bytes();
+        assert_eq!(decoder.feed(&bytes[..1]), vec![]);
+        assert_eq!(decoder.feed(&bytes[1..6]), vec![Event::key(KeyCode::Char('Ж')), Event::key(KeyCode::Char('x'))]);
+        assert_eq!(decoder.feed(&bytes[6..]), vec![Event::Key(KeyEvent::ctrl(KeyCode::Right))]);
+        assert_eq!(decoder.feed(b"\x1bO"), vec![]);
+        assert_eq!(
+            decoder.feed(b"P\xff\x1b"),
+            vec![Event::key(KeyCode::F(1)), Event::key(KeyCode::Char('\u{FFFD}')), Event::key(KeyCode::Esc)]
+        );
+    }
+}
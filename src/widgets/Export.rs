@@ -0,0 +1,295 @@
+use crate::widgets::canvas::{Canvas, Cell};
+use crate::widgets::layout::{Constraints, Rect};
+use crate::widgets::style::{Color, Style};
+use crate::widgets::Widget;
+
+/// Превращает нарисованный холст в документ того или иного формата.
+pub trait Renderer {
+    fn render(&self, canvas: &Canvas) -> String;
+}
+
+/// Рисует виджет в его собственном размере и передаёт холст рендереру.
+pub fn export(widget: &dyn Widget, renderer: &dyn Renderer) -> String {
+    let area = Rect::from_size(widget.measure(Constraints::unbounded()));
+    let mut canvas = Canvas::new(area.size());
+    widget.render(&mut canvas, area);
+    renderer.render(&canvas)
+}
+
+/// Соседние клетки строки с одинаковым стилем, без вторых половин широких символов.
+fn runs(row: &[Cell]) -> Vec<(Style, String, usize)> {
+    let mut runs: Vec<(Style, String, usize)> = Vec::new();
+    for cell in row {
+        match runs.last_mut() {
+            Some((style, text, width)) if *style == cell.style => {
+                text.push_str(&cell.symbol);
+                *width += 1;
+            }
+            _ => runs.push((cell.style, cell.symbol.clone(), 1)),
+        }
+    }
+    runs
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Цвета текста и фона с учётом инверсии; `None` — цвет по умолчанию.
+fn colors(style: Style, foreground: Color, background: Color) -> (Option<Color>, Option<Color>) {
+    if style.reverse {
+        (Some(style.bg.unwrap_or(background)), Some(style.fg.unwrap_or(foreground)))
+    } else {
+        (style.fg, style.bg)
+    }
+}
+
+/// Тот же вывод, что и на терминале, с escape-последовательностями ANSI.
+pub struct AnsiRenderer;
+
+impl Renderer for AnsiRenderer {
+    fn render(&self, canvas: &Canvas) -> String {
+        canvas.to_string()
+    }
+}
+
+/// Обычный текст без оформления. С `strip_borders` рамки и разделители стираются,
+/// опустевшие строки выбрасываются, а общий отступ слева убирается.
+pub struct PlainRenderer {
+    pub strip_borders: bool,
+}
+
+impl Renderer for PlainRenderer {
+    fn render(&self, canvas: &Canvas) -> String {
+        let mut lines = Vec::new();
+        for (y, row) in canvas.rows().enumerate() {
+            let mut line = String::new();
+            let mut stripped = false;
+            for (x, cell) in row.iter().enumerate() {
+                if self.strip_borders && canvas.is_border(x, y) {
+                    stripped = true;
+                    line.push(' ');
+                } else {
+                    line.push_str(&cell.symbol);
+                }
+            }
+            let line = line.trim_end().to_owned();
+            if !(stripped && line.is_empty()) {
+                lines.push(line);
+            }
+        }
+        if self.strip_borders {
+            let indent = lines
+                .iter()
+                .filter(|line| !line.is_empty())
+                .map(|line| line.len() - line.trim_start_matches(' ').len())
+                .min()
+                .unwrap_or(0);
+            for line in &mut lines {
+                *line = line.get(indent..).unwrap_or_default().to_owned();
+            }
+        }
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+}
+
+/// Самостоятельная HTML-страница: блок `<pre>` с раскрашенными `<span>`.
+pub struct HtmlRenderer {
+    pub title: String,
+    pub foreground: Color,
+    pub background: Color,
+}
+
+impl Default for HtmlRenderer {
+    fn default() -> HtmlRenderer {
+        HtmlRenderer { title: String::new(), foreground: Color::White, background: Color::Black }
+    }
+}
+
+impl HtmlRenderer {
+    fn css(&self, style: Style) -> String {
+        let (fg, bg) = colors(style, self.foreground, self.background);
+        let mut css = Vec::new();
+        if let Some(fg) = fg {
+            css.push(format!("color:{}", fg.to_hex()));
+        }
+        if let Some(bg) = bg {
+            css.push(format!("background:{}", bg.to_hex()));
+        }
+        if style.bold {
+            css.push("font-weight:bold".to_owned());
+        }
+        if style.dim {
+            css.push("opacity:0.6".to_owned());
+        }
+        if style.italic {
+            css.push("font-style:italic".to_owned());
+        }
+        let decorations: Vec<&str> = [(style.underline, "underline"), (style.strikethrough, "line-through")]
+            .into_iter()
+            .filter(|(on, _)| *on)
+            .map(|(_, name)| name)
+            .collect();
+        if !decorations.is_empty() {
+            css.push(format!("text-decoration:{}", decorations.join(" ")));
+        }
+        css.join(";")
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, canvas: &Canvas) -> String {
+        let mut body = String::new();
+        for row in canvas.rows() {
+            for (style, text, _) in runs(row) {
+                let css = self.css(style);
+                if css.is_empty() {
+                    body.push_str(&escape_xml(&text));
+                } else {
+                    body.push_str(&format!("<span style=\"{}\">{}</span>", css, escape_xml(&text)));
+                }
+            }
+            body.push('\n');
+        }
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+             pre {{ font-family: monospace; line-height: 1.2; padding: 1em; color: {}; background: {}; }}\n\
+             </style>\n</head>\n<body>\n<pre>\n{}</pre>\n</body>\n</html>\n",
+            escape_xml(&self.title),
+            self.foreground.to_hex(),
+            self.background.to_hex(),
+            body
+        )
+    }
+}
+
+/// Картинка терминала в SVG: клетка фиксированного размера, фон — прямоугольниками,
+/// текст растянут по ширине своих клеток, чтобы шрифт не сбивал сетку.
+pub struct SvgRenderer {
+    pub cell_width: usize,
+    pub cell_height: usize,
+    pub foreground: Color,
+    pub background: Color,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> SvgRenderer {
+        SvgRenderer { cell_width: 9, cell_height: 18, foreground: Color::White, background: Color::Black }
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn render(&self, canvas: &Canvas) -> String {
+        let (w, h) = (self.cell_width, self.cell_height);
+        let size = canvas.size();
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"{}\">\n",
+            size.width * w,
+            size.height * h,
+            h * 3 / 4
+        );
+        out.push_str(&format!("<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n", self.background.to_hex()));
+        for (y, row) in canvas.rows().enumerate() {
+            let mut x = 0;
+            for (style, text, width) in runs(row) {
+                let (fg, bg) = colors(style, self.foreground, self.background);
+                if let Some(bg) = bg {
+                    out.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                        x * w,
+                        y * h,
+                        width * w,
+                        h,
+                        bg.to_hex()
+                    ));
+                }
+                if !text.trim().is_empty() {
+                    let mut attributes = format!("fill=\"{}\"", fg.unwrap_or(self.foreground).to_hex());
+                    if style.bold {
+                        attributes.push_str(" font-weight=\"bold\"");
+                    }
+                    if style.italic {
+                        attributes.push_str(" font-style=\"italic\"");
+                    }
+                    if style.underline {
+                        attributes.push_str(" text-decoration=\"underline\"");
+                    }
+                    if style.dim {
+                        attributes.push_str(" opacity=\"0.6\"");
+                    }
+                    out.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" xml:space=\"preserve\" {}>{}</text>\n",
+                        x * w,
+                        y * h + h * 3 / 4,
+                        width * w,
+                        attributes,
+                        escape_xml(&text)
+                    ));
+                }
+                x += width;
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::{Button, Label, Table, Window};
+
+    fn window() -> Window {
+        let mut window = Window::new("Отчёт");
+        let mut label = Label::new("a < b & c");
+        label.set_style(Style::new().fg(Color::Red).bold());
+        window.add_widget(Box::new(label));
+        window.add_widget(Box::new(Button::new("OK")));
+        window
+    }
+
+    #[test]
+    fn plain_text_without_borders() {
+        assert_eq!(
+            export(&window(), &PlainRenderer { strip_borders: true }),
+            "   Отчёт\na < b & c\n     OK\n"
+        );
+        let mut table = Table::new(&["Имя", "Код"]);
+        table.add_text_row(&["Анна", "7"]);
+        assert_eq!(export(&table, &PlainRenderer { strip_borders: true }), "Имя    Код\nАнна   7\n");
+        assert!(export(&window(), &PlainRenderer { strip_borders: false }).starts_with("+---"));
+    }
+
+    #[test]
+    fn html_page() {
+        let html = export(&window(), &HtmlRenderer { title: "Макет".to_owned(), ..HtmlRenderer::default() });
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Макет</title>"));
+        assert!(html.contains("<span style=\"color:#cd0000;font-weight:bold\">a &lt; b &amp; c</span>"), "{}", html);
+    }
+
+    #[test]
+    fn svg_grid() {
+        let svg = export(&Label::new("ab"), &SvgRenderer::default());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"18\" height=\"18\""));
+        assert!(svg.contains("<text x=\"0\" y=\"13\" textLength=\"18\""));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn palette() {
+        assert_eq!(Color::Indexed(196).to_hex(), "#ff0000");
+        assert_eq!(Color::Indexed(244).to_hex(), "#808080");
+        assert_eq!(Color::Rgb(1, 2, 3).to_hex(), "#010203");
+    }
+}
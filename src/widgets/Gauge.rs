@@ -0,0 +1,133 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::layout::{fit, Align, Rect};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Шкала заполнения: закрашенная слева часть области и подпись по центру,
+/// по умолчанию — процент. Занимает всю выделенную ей высоту.
+pub struct Gauge {
+    width: usize,
+    height: usize,
+    ratio: f64,
+    label: Option<String>,
+    style: Themed<Style>,
+    filled_style: Themed<Style>,
+    dirty: bool,
+}
+
+impl Gauge {
+    pub fn new(width: usize) -> Gauge {
+        Gauge {
+            width,
+            height: 1,
+            ratio: 0.0,
+            label: None,
+            style: Themed::new(Style::default()),
+            filled_style: Themed::new(Style::new().reverse()),
+            dirty: false,
+        }
+    }
+
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Доля заполнения; значения вне 0..1 обрезаются.
+    pub fn set_ratio(&mut self, ratio: f64) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self.dirty = true;
+    }
+
+    /// Заполнение как часть `value` от `max`.
+    pub fn set_value(&mut self, value: u64, max: u64) {
+        self.set_ratio(if max == 0 { 1.0 } else { value as f64 / max as f64 });
+    }
+
+    pub fn set_height(&mut self, height: usize) {
+        self.height = height;
+    }
+
+    /// Подпись вместо процента.
+    pub fn set_label(&mut self, label: Option<&str>) {
+        self.label = label.map(str::to_owned);
+        self.dirty = true;
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_filled_style(&mut self, style: Style) {
+        self.filled_style.set(style);
+    }
+}
+
+impl Widget for Gauge {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn accessible(&self) -> Accessible {
+        let percent = format!("{}%", (self.ratio * 100.0).round() as u64);
+        Accessible::new(AccessRole::ProgressBar, self.label.as_deref().unwrap_or("")).with_value(percent)
+    }
+
+    /// Заполненная часть пуста и видна только фоном, поэтому берёт стиль выделения, а не `Accent`.
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Label));
+        self.filled_style.theme(theme.style(Role::ButtonFocused));
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let label = match &self.label {
+            Some(label) => label.clone(),
+            None => format!("{}%", (self.ratio * 100.0).round() as u64),
+        };
+        let style = self.style.get();
+        canvas.fill(area, " ", style);
+        if area.height > 0 {
+            canvas.put_str(area.x, area.y + area.height / 2, &fit(&label, area.width, Align::Center), style);
+        }
+        let filled = (area.width as f64 * self.ratio).round() as usize;
+        canvas.set_style(Rect::new(area.x, area.y, filled, area.height), self.filled_style.get());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::layout::Size;
+    use crate::widgets::snapshot::{plain, render};
+
+    #[test]
+    fn fill_and_label() {
+        let mut gauge = Gauge::new(10);
+        gauge.set_value(3, 4);
+        let mut canvas = Canvas::new(Size::new(10, 3));
+        gauge.render(&mut canvas, Rect::new(0, 0, 10, 3));
+        assert_eq!(plain(&canvas).lines().nth(1), Some("   75%    "));
+        assert!(canvas.get(7, 0).unwrap().style.reverse);
+        assert!(canvas.get(7, 2).unwrap().style.reverse);
+        assert!(!canvas.get(8, 1).unwrap().style.reverse);
+
+        gauge.set_label(Some("диск"));
+        gauge.set_ratio(2.0);
+        assert_eq!(gauge.ratio(), 1.0);
+        let out = render(&gauge);
+        assert_eq!(out, "␛[7m   диск   ␛[0m\n");
+    }
+}
@@ -0,0 +1,216 @@
+use crate::widgets::document::{Key, Kind, LoadError, Reader, Value};
+
+/// Разбирает JSON-документ. Числа допускаются только целые, `null` не поддерживается.
+pub fn parse(text: &str) -> Result<Value, LoadError> {
+    let mut reader = Reader::new(text);
+    let value = parse_value(&mut reader)?;
+    skip_space(&mut reader);
+    match reader.peek() {
+        None => Ok(value),
+        Some(_) => Err(reader.unexpected("конец документа")),
+    }
+}
+
+fn skip_space(reader: &mut Reader) {
+    reader.skip_while(|c| matches!(c, ' ' | '\t' | '\r' | '\n'));
+}
+
+fn parse_value(reader: &mut Reader) -> Result<Value, LoadError> {
+    skip_space(reader);
+    let position = reader.position();
+    let kind = match reader.peek() {
+        Some('{') => {
+            reader.next();
+            let mut entries = Vec::new();
+            skip_space(reader);
+            if !reader.eat('}') {
+                loop {
+                    skip_space(reader);
+                    let key = Key { position: reader.position(), name: parse_string(reader)? };
+                    if entries.iter().any(|(other, _): &(Key, Value)| other.name == key.name) {
+                        return Err(LoadError::new(format!("ключ `{}` повторяется", key.name), key.position));
+                    }
+                    skip_space(reader);
+                    reader.expect(':')?;
+                    entries.push((key, parse_value(reader)?));
+                    skip_space(reader);
+                    if reader.eat('}') {
+                        break;
+                    }
+                    reader.expect(',')?;
+                }
+            }
+            Kind::Table(entries)
+        }
+        Some('[') => {
+            reader.next();
+            let mut items = Vec::new();
+            skip_space(reader);
+            if !reader.eat(']') {
+                loop {
+                    items.push(parse_value(reader)?);
+                    skip_space(reader);
+                    if reader.eat(']') {
+                        break;
+                    }
+                    reader.expect(',')?;
+                }
+            }
+            Kind::Array(items)
+        }
+        Some('"') => Kind::String(parse_string(reader)?),
+        Some(c) if c == '-' || c.is_ascii_digit() => {
+            let mut digits = String::new();
+            if reader.eat('-') {
+                digits.push('-');
+            }
+            while let Some(c) = reader.peek().filter(|c| c.is_ascii_digit()) {
+                digits.push(c);
+                reader.next();
+            }
+            if matches!(reader.peek(), Some('.' | 'e' | 'E')) {
+                return Err(reader.error("поддерживаются только целые числа"));
+            }
+            Kind::Integer(digits.parse().map_err(|_| LoadError::new("неверное число", position))?)
+        }
+        Some(c) if c.is_ascii_alphabetic() => {
+            let mut word = String::new();
+            while let Some(c) = reader.peek().filter(|c| c.is_ascii_alphabetic()) {
+                word.push(c);
+                reader.next();
+            }
+            match word.as_str() {
+                "true" => Kind::Boolean(true),
+                "false" => Kind::Boolean(false),
+                "null" => return Err(LoadError::new("`null` не поддерживается", position)),
+                _ => return Err(LoadError::new(format!("неизвестное слово `{}`", word), position)),
+            }
+        }
+        _ => return Err(reader.unexpected("значение")),
+    };
+    Ok(Value::new(kind, position))
+}
+
+fn parse_string(reader: &mut Reader) -> Result<String, LoadError> {
+    reader.expect('"')?;
+    let mut text = String::new();
+    loop {
+        match reader.next() {
+            Some('"') => return Ok(text),
+            Some('\\') => {
+                let position = reader.position();
+                let c = match reader.next() {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('/') => '/',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('u') => {
+                        let mut code = reader.hex_escape(4)?;
+                        // суррогатная пара: \uD83D\uDE00
+                        if (0xD800..0xDC00).contains(&code) && reader.looking_at("\\u") {
+                            reader.next();
+                            reader.next();
+                            let low = reader.hex_escape(4)?;
+                            if !(0xDC00..0xE000).contains(&low) {
+                                return Err(LoadError::new("за первой половиной суррогатной пары нет второй", position));
+                            }
+                            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                        }
+                        char::from_u32(code).ok_or_else(|| LoadError::new("неверный код символа", position))?
+                    }
+                    _ => return Err(LoadError::new("неизвестная escape-последовательность", position)),
+                };
+                text.push(c);
+            }
+            Some(c) if (c as u32) < 0x20 => return Err(reader.error("управляющий символ внутри строки")),
+            Some(c) => text.push(c),
+            None => return Err(reader.error("строка не закрыта")),
+        }
+    }
+}
+
+/// Строка в двойных кавычках с экранированием — годится и для JSON, и для TOML.
+pub fn quote(text: &str) -> String {
+    let mut out = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\x7f' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Записывает значение с отступом в два пробела.
+pub fn write(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(value, 0, &mut out);
+    out.push('\n');
+    out
+}
+
+fn write_value(value: &Value, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth + 1);
+    match &value.kind {
+        Kind::String(text) => out.push_str(&quote(text)),
+        Kind::Integer(n) => out.push_str(&n.to_string()),
+        Kind::Boolean(flag) => out.push_str(&flag.to_string()),
+        Kind::Array(items) if items.is_empty() => out.push_str("[]"),
+        Kind::Array(items) => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(&indent);
+                write_value(item, depth + 1, out);
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&format!("{}]", "  ".repeat(depth)));
+        }
+        Kind::Table(entries) if entries.is_empty() => out.push_str("{}"),
+        Kind::Table(entries) => {
+            out.push_str("{\n");
+            for (i, (key, item)) in entries.iter().enumerate() {
+                out.push_str(&format!("{}{}: ", indent, quote(&key.name)));
+                write_value(item, depth + 1, out);
+                out.push_str(if i + 1 < entries.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&format!("{}}}", "  ".repeat(depth)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_write() {
+        let value = parse(r#"{"a": [1, -2, true], "b": {"c": "\"x\"\n\u00e9\ud83d\ude00"}}"#).unwrap();
+        assert_eq!(value.get("b").unwrap().get("c").unwrap().kind, Kind::String("\"x\"\né😀".to_owned()));
+        assert_eq!(
+            write(&value),
+            "{\n  \"a\": [\n    1,\n    -2,\n    true\n  ],\n  \"b\": {\n    \"c\": \"\\\"x\\\"\\né😀\"\n  }\n}\n"
+        );
+        assert_eq!(parse(&write(&value)).map(|v| write(&v)), Ok(write(&value)));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let error = parse("{\n  \"a\": 1\n  \"b\": 2\n}").unwrap_err();
+        assert_eq!(error.to_string(), "строка 3, столбец 3: ожидалось `,`, а встретилось `\"`");
+        assert_eq!(parse("[1.5]").unwrap_err().message, "поддерживаются только целые числа");
+        assert_eq!(parse("{\"a\": 1, \"a\": 2}").unwrap_err().position.column, 10);
+        let error = parse(r#""\uD83D\u0041""#).unwrap_err();
+        assert_eq!(error.message, "за первой половиной суррогатной пары нет второй");
+        assert_eq!(error.position.column, 3);
+    }
+}
@@ -1,21 +1,264 @@
-use std::fmt::Write;
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::{align_name, wrap_name, Value};
+use crate::widgets::layout::{ellipsize, fit, text_width, truncate, wrap, Align, Constraints, Rect, Size, Wrap};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
 
+/// Текст из одной или нескольких строк. Длинные абзацы переносятся по ширине,
+/// которую отводит родитель, или по `max_width`.
 pub struct Label {
     label: String,
+    style: Themed<Style>,
+    role: Role,
+    wrap: Wrap,
+    align: Align,
+    ellipsis: bool,
+    max_width: Option<usize>,
 }
 
 impl Label {
     pub fn new(label: &str) -> Label {
-        Label { label: label.to_owned() }
+        Label {
+            label: label.to_owned(),
+            style: Themed::new(Style::default()),
+            role: Role::Label,
+            wrap: Wrap::None,
+            align: Align::Left,
+            ellipsis: false,
+            max_width: None,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.label
+    }
+
+    pub fn set_text(&mut self, label: &str) {
+        self.label = label.to_owned();
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    /// Роль, по которой тема выбирает стиль: обычная надпись или сообщение об ошибке.
+    pub fn set_role(&mut self, role: Role) {
+        self.role = role;
+    }
+
+    pub fn set_wrap(&mut self, wrap: Wrap) {
+        self.wrap = wrap;
+    }
+
+    /// Выравнивание строк; `Justify` не растягивает последнюю строку абзаца.
+    pub fn set_align(&mut self, align: Align) {
+        self.align = align;
+    }
+
+    /// Текст, не поместившийся в область, заканчивается многоточием.
+    pub fn set_ellipsis(&mut self, ellipsis: bool) {
+        self.ellipsis = ellipsis;
+    }
+
+    pub fn set_max_width(&mut self, max_width: Option<usize>) {
+        self.max_width = max_width;
+    }
+
+    /// Строки при ширине `width` и признак того, что строка завершает абзац.
+    fn lines(&self, width: usize) -> Vec<(String, bool)> {
+        let mut lines = Vec::new();
+        for paragraph in self.label.lines() {
+            let wrapped = wrap(paragraph, width, self.wrap);
+            let last = wrapped.len() - 1;
+            lines.extend(wrapped.into_iter().enumerate().map(|(i, line)| (line, i == last)));
+        }
+        lines
+    }
+
+    /// Ширина самой длинной строки без переносов.
+    fn natural_width(&self) -> usize {
+        self.lines(usize::MAX).iter().map(|(line, _)| text_width(line)).max().unwrap_or(0)
+    }
+
+    fn limit(&self, width: usize) -> usize {
+        self.max_width.map_or(width, |max_width| max_width.min(width))
+    }
+}
+
+/// Пошаговая сборка `Label`: `Label::builder().text("…").wrap(Wrap::Word).build()`.
+pub struct LabelBuilder {
+    label: Label,
+}
+
+impl Label {
+    pub fn builder() -> LabelBuilder {
+        LabelBuilder { label: Label::new("") }
+    }
+}
+
+impl LabelBuilder {
+    pub fn text(mut self, text: &str) -> LabelBuilder {
+        self.label.set_text(text);
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> LabelBuilder {
+        self.label.set_style(style);
+        self
+    }
+
+    pub fn role(mut self, role: Role) -> LabelBuilder {
+        self.label.set_role(role);
+        self
+    }
+
+    pub fn wrap(mut self, wrap: Wrap) -> LabelBuilder {
+        self.label.set_wrap(wrap);
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> LabelBuilder {
+        self.label.set_align(align);
+        self
+    }
+
+    pub fn ellipsis(mut self, ellipsis: bool) -> LabelBuilder {
+        self.label.set_ellipsis(ellipsis);
+        self
+    }
+
+    pub fn max_width(mut self, max_width: usize) -> LabelBuilder {
+        self.label.set_max_width(Some(max_width));
+        self
+    }
+
+    pub fn build(self) -> Label {
+        self.label
     }
 }
 
 impl super::Widget for Label {
     fn width(&self) -> usize {
-        self.label.lines().map(|line| line.chars().count()).max().unwrap_or(0)
+        self.limit(self.natural_width())
+    }
+
+    fn height(&self) -> usize {
+        self.lines(self.width()).len()
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::Label, &self.label)
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        let width = self.limit(constraints.max_width).min(self.natural_width());
+        let height = match self.wrap {
+            Wrap::None => self.label.lines().count(),
+            _ => self.lines(width).len(),
+        };
+        constraints.constrain(Size::new(width, height))
     }
 
-    fn draw_into(&self, buffer: &mut dyn Write) {
-        writeln!(buffer, "{}", &self.label).unwrap();
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let lines = self.lines(area.width);
+        let overflow = lines.len() > area.height;
+        for (i, (y, (line, end))) in (area.y..area.bottom()).zip(&lines).enumerate() {
+            let line = if self.ellipsis && overflow && i + 1 == area.height {
+                // дальше есть невидимый текст — отмечаем это на последней видимой строке
+                format!("{}…", truncate(line, area.width.saturating_sub(1)))
+            } else if self.ellipsis {
+                ellipsize(line, area.width)
+            } else {
+                line.clone()
+            };
+            let align = if *end && self.align == Align::Justify { Align::Left } else { self.align };
+            let free = area.width.saturating_sub(text_width(&line));
+            let (x, line) = match align {
+                Align::Left => (area.x, line),
+                Align::Center => (area.x + free / 2, line),
+                Align::Right => (area.x + free, line),
+                Align::Justify => (area.x, fit(&line, area.width, Align::Justify)),
+            };
+            canvas.put_str(x, y, &line, self.style.get());
+        }
     }
-}
\ No newline at end of file
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(self.role));
+    }
+
+    fn describe(&self) -> Option<Value> {
+        let mut properties = vec![("type", Value::from("label")), ("text", Value::from(self.label.as_str()))];
+        // сохраняются только отличия от значений по умолчанию
+        if self.wrap != Wrap::None {
+            properties.push(("wrap", Value::from(wrap_name(self.wrap))));
+        }
+        if self.align != Align::Left {
+            properties.push(("align", Value::from(align_name(self.align))));
+        }
+        if self.ellipsis {
+            properties.push(("ellipsis", Value::from(true)));
+        }
+        if let Some(max_width) = self.max_width {
+            properties.push(("max_width", Value::from(max_width)));
+        }
+        Some(Value::table(properties))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::layout::Size;
+    use crate::widgets::style::Color;
+    use crate::widgets::Widget;
+    use crate::widgets::snapshot::render_sized;
+
+    #[test]
+    fn styled_label_keeps_width() {
+        let mut label = Label::new("ошибка");
+        label.set_style(Style::new().fg(Color::Red));
+        assert_eq!(label.width(), 6);
+        assert_eq!(render_sized(&label, Size::new(8, 1)), "␛[31mошибка␛[0m  \n");
+    }
+
+    #[test]
+    fn clipped_to_area() {
+        let label = Label::new("первая\nвторая\nтретья");
+        let out = render_sized(&label, Size::new(3, 2));
+        assert_eq!(out, "пер\nвто\n");
+    }
+
+    #[test]
+    fn wraps_to_parent_width() {
+        let mut label = Label::new("Нажмите клавишу F1, чтобы открыть справку");
+        label.set_wrap(Wrap::Word);
+        assert_eq!(label.measure(Constraints::loose(Size::new(16, 10))), Size::new(16, 3));
+        assert_eq!(render_sized(&label, Size::new(16, 3)), "Нажмите клавишу \nF1, чтобы       \nоткрыть справку \n");
+        label.set_max_width(Some(12));
+        assert_eq!(label.size_hint(), Size::new(12, 5));
+    }
+
+    #[test]
+    fn justify_keeps_last_line() {
+        let mut label = Label::new("раз два три четыре");
+        label.set_wrap(Wrap::Word);
+        label.set_align(Align::Justify);
+        assert_eq!(render_sized(&label, Size::new(10, 2)), "раз    два\nтри четыре\n");
+    }
+
+    #[test]
+    fn ellipsis() {
+        let mut label = Label::new("очень длинная строка\nещё одна");
+        label.set_ellipsis(true);
+        assert_eq!(render_sized(&label, Size::new(8, 2)), "очень д…\nещё одна\n");
+        label.set_wrap(Wrap::Word);
+        assert_eq!(render_sized(&label, Size::new(8, 2)), "очень   \nдлинная…\n");
+        label.set_align(Align::Right);
+        label.set_wrap(Wrap::None);
+        label.set_ellipsis(false);
+        label.set_text("ок");
+        assert_eq!(render_sized(&label, Size::new(4, 1)), "  ок\n");
+    }
+}
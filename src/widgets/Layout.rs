@@ -0,0 +1,366 @@
+use std::cmp::{max, min};
+use crate::widgets::style::RESET;
+use crate::widgets::unicode::{grapheme_width, graphemes, str_width};
+
+/// Размер виджета в символах: ширина в столбцах, высота в строках.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Size {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Size {
+    pub fn new(width: usize, height: usize) -> Size {
+        Size { width, height }
+    }
+}
+
+impl From<(usize, usize)> for Size {
+    fn from((width, height): (usize, usize)) -> Size {
+        Size::new(width, height)
+    }
+}
+
+/// Прямоугольная область на холсте: левый верхний угол и размер.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Rect {
+        Rect { x, y, width, height }
+    }
+
+    /// Область размера `size` в начале координат.
+    pub fn from_size(size: Size) -> Rect {
+        Rect::new(0, 0, size.width, size.height)
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    /// Первый столбец справа за пределами области.
+    pub fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    /// Первая строка снизу за пределами области.
+    pub fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Пересечение двух областей (пустое, если они не пересекаются).
+    pub fn intersect(&self, other: Rect) -> Rect {
+        let x = max(self.x, other.x);
+        let y = max(self.y, other.y);
+        let right = min(self.right(), other.right());
+        let bottom = min(self.bottom(), other.bottom());
+        Rect::new(x, y, right.saturating_sub(x), bottom.saturating_sub(y))
+    }
+
+    /// Область, уменьшенная на `horizontal` столбцов и `vertical` строк с каждой стороны.
+    pub fn inset(&self, horizontal: usize, vertical: usize) -> Rect {
+        Rect::new(
+            self.x + horizontal,
+            self.y + vertical,
+            self.width.saturating_sub(2 * horizontal),
+            self.height.saturating_sub(2 * vertical),
+        )
+    }
+
+    /// Область размера `size` по центру этой; не больше её самой.
+    pub fn centered(&self, size: Size) -> Rect {
+        let width = min(size.width, self.width);
+        let height = min(size.height, self.height);
+        Rect::new(self.x + (self.width - width) / 2, self.y + (self.height - height) / 2, width, height)
+    }
+}
+
+/// Ограничения, которые родитель передаёт дочернему виджету при измерении.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Constraints {
+    pub min_width: usize,
+    pub max_width: usize,
+    pub min_height: usize,
+    pub max_height: usize,
+}
+
+impl Constraints {
+    /// Без ограничений: виджет получает столько места, сколько просит.
+    pub fn unbounded() -> Constraints {
+        Constraints { min_width: 0, max_width: usize::MAX, min_height: 0, max_height: usize::MAX }
+    }
+
+    /// Жёсткий размер: минимум и максимум совпадают.
+    pub fn tight(size: Size) -> Constraints {
+        Constraints {
+            min_width: size.width,
+            max_width: size.width,
+            min_height: size.height,
+            max_height: size.height,
+        }
+    }
+
+    /// Только верхняя граница, минимум нулевой.
+    pub fn loose(size: Size) -> Constraints {
+        Constraints { min_width: 0, max_width: size.width, min_height: 0, max_height: size.height }
+    }
+
+    /// Приводит желаемый размер к допустимому диапазону.
+    pub fn constrain(&self, size: Size) -> Size {
+        Size {
+            width: min(max(size.width, self.min_width), self.max_width),
+            height: min(max(size.height, self.min_height), self.max_height),
+        }
+    }
+
+    /// Ограничения для содержимого после вычета рамок и отступов.
+    pub fn shrink(&self, horizontal: usize, vertical: usize) -> Constraints {
+        Constraints {
+            min_width: self.min_width.saturating_sub(horizontal),
+            max_width: self.max_width.saturating_sub(horizontal),
+            min_height: self.min_height.saturating_sub(vertical),
+            max_height: self.max_height.saturating_sub(vertical),
+        }
+    }
+}
+
+/// Выравнивание строки внутри отведённой ширины.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+    /// Пробелы между словами растягиваются до полной ширины.
+    Justify,
+}
+
+/// Режим переноса длинных строк.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Wrap {
+    /// Строки не переносятся и обрезаются по краю.
+    #[default]
+    None,
+    /// Перенос в любом месте строки.
+    Char,
+    /// Перенос по пробелам и мягким переносам (U+00AD).
+    Word,
+}
+
+/// Мягкий перенос: невидим, но допускает разрыв слова с дефисом.
+pub const SOFT_HYPHEN: char = '\u{AD}';
+
+/// Ширина строки в столбцах терминала.
+pub fn text_width(text: &str) -> usize {
+    str_width(text)
+}
+
+/// Обрезает строку до `width` столбцов, не разрывая графемные кластеры.
+/// Широкий символ, который не помещается целиком, отбрасывается. Если строка
+/// была оформлена escape-последовательностями, после обрезки стиль сбрасывается.
+pub fn truncate(text: &str, width: usize) -> String {
+    let mut used = 0;
+    let mut result = String::new();
+    for grapheme in graphemes(text) {
+        used += grapheme_width(grapheme);
+        if used > width {
+            if result.contains('\x1b') {
+                result.push_str(RESET);
+            }
+            break;
+        }
+        result.push_str(grapheme);
+    }
+    result
+}
+
+/// Дополняет (или обрезает) строку пробелами ровно до `width` столбцов.
+pub fn fit(text: &str, width: usize, align: Align) -> String {
+    let text = truncate(text, width);
+    let free = width - text_width(&text);
+    let (left, right) = match align {
+        Align::Left => (0, free),
+        Align::Justify => return justify(&text, width),
+        Align::Center => (free / 2, free - free / 2),
+        Align::Right => (free, 0),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+/// Растягивает пробелы между словами так, чтобы строка заняла ровно `width` столбцов;
+/// лишние пробелы достаются левым промежуткам. Строка из одного слова выравнивается влево.
+fn justify(text: &str, width: usize) -> String {
+    let words: Vec<&str> = text.split(' ').filter(|word| !word.is_empty()).collect();
+    let used: usize = words.iter().map(|word| text_width(word)).sum();
+    if words.len() < 2 || used >= width {
+        return fit(text, width, Align::Left);
+    }
+    let gaps = words.len() - 1;
+    let (space, extra) = ((width - used) / gaps, (width - used) % gaps);
+    let mut line = String::new();
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            line.push_str(&" ".repeat(space + (i <= extra) as usize));
+        }
+        line.push_str(word);
+    }
+    line
+}
+
+/// Обрезает строку до `width` столбцов, заменяя отрезанный хвост многоточием.
+pub fn ellipsize(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return text.to_owned();
+    }
+    format!("{}…", truncate(text, width.saturating_sub(1)))
+}
+
+/// Разбивает одну строку (абзац) на строки не шире `width` столбцов.
+/// Мягкие переносы из результата убираются; там, где по ним разорвано слово, ставится дефис.
+pub fn wrap(text: &str, width: usize, mode: Wrap) -> Vec<String> {
+    let width = width.max(1);
+    match mode {
+        Wrap::None => vec![text.replace(SOFT_HYPHEN, "")],
+        Wrap::Char => wrap_chars(&text.replace(SOFT_HYPHEN, ""), width),
+        Wrap::Word => wrap_words(text, width),
+    }
+}
+
+fn wrap_chars(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut used = 0;
+    for grapheme in graphemes(text) {
+        let grapheme_width = grapheme_width(grapheme);
+        if used + grapheme_width > width && used > 0 {
+            lines.push(std::mem::take(&mut line));
+            used = 0;
+        }
+        line.push_str(grapheme);
+        used += grapheme_width;
+    }
+    lines.push(line);
+    lines
+}
+
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let visible = |text: &str| text.replace(SOFT_HYPHEN, "");
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ').filter(|word| !word.is_empty()) {
+        let mut word = word;
+        loop {
+            let used = text_width(&line) + !line.is_empty() as usize;
+            let space = if line.is_empty() { "" } else { " " };
+            if used + text_width(&visible(word)) <= width {
+                line = format!("{}{}{}", line, space, visible(word));
+                break;
+            }
+            // самый длинный кусок до мягкого переноса, после которого ещё влезает дефис
+            let hyphen = word
+                .match_indices(SOFT_HYPHEN)
+                .map(|(i, _)| i)
+                .rev()
+                .find(|&i| used + text_width(&visible(&word[..i])) < width);
+            if let Some(i) = hyphen {
+                lines.push(format!("{}{}{}-", line, space, visible(&word[..i])));
+                line.clear();
+                word = &word[i + SOFT_HYPHEN.len_utf8()..];
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                continue;
+            }
+            // слово длиннее строки рубится посимвольно
+            let mut pieces = wrap_chars(&visible(word), width);
+            line = pieces.pop().unwrap_or_default();
+            lines.extend(pieces);
+            break;
+        }
+    }
+    lines.push(line);
+    lines
+}
+
+/// Приводит многострочный вывод к прямоугольнику `size`: лишние строки
+/// отбрасываются, недостающие добавляются пустыми.
+pub fn fit_lines(text: &str, size: Size, align: Align) -> Vec<String> {
+    let mut lines: Vec<String> =
+        text.lines().take(size.height).map(|line| fit(line, size.width, align)).collect();
+    lines.resize(size.height, " ".repeat(size.width));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constrain() {
+        let c = Constraints { min_width: 5, max_width: 10, min_height: 1, max_height: 3 };
+        assert_eq!(c.constrain(Size::new(2, 0)), Size::new(5, 1));
+        assert_eq!(c.constrain(Size::new(20, 7)), Size::new(10, 3));
+        assert_eq!(c.constrain(Size::new(7, 2)), Size::new(7, 2));
+    }
+
+    #[test]
+    fn rect_intersect() {
+        let a = Rect::new(0, 0, 4, 4);
+        assert_eq!(a.intersect(Rect::new(2, 3, 10, 10)), Rect::new(2, 3, 2, 1));
+        assert!(a.intersect(Rect::new(5, 5, 1, 1)).is_empty());
+        assert_eq!(a.inset(1, 1), Rect::new(1, 1, 2, 2));
+    }
+
+    #[test]
+    fn wrap_modes() {
+        assert_eq!(wrap("абвгде", 4, Wrap::Char), vec!["абвг", "де"]);
+        assert_eq!(wrap("раз два  три", 7, Wrap::Word), vec!["раз два", "три"]);
+        assert_eq!(wrap("очень-длинное", 5, Wrap::Word), vec!["очень", "-длин", "ное"]);
+        let hinted = "про\u{AD}грам\u{AD}ма";
+        assert_eq!(wrap(hinted, 10, Wrap::None), vec!["программа"]);
+        assert_eq!(wrap(&format!("это {}", hinted), 9, Wrap::Word), vec!["это про-", "грамма"]);
+    }
+
+    #[test]
+    fn justify_and_ellipsis() {
+        assert_eq!(fit("a b c", 8, Align::Justify), "a   b  c");
+        assert_eq!(fit("слово", 8, Align::Justify), "слово   ");
+        assert_eq!(ellipsize("длинный текст", 8), "длинный…");
+        assert_eq!(ellipsize("коротко", 8), "коротко");
+    }
+
+    #[test]
+    fn fit_pads_and_truncates() {
+        assert_eq!(fit("ab", 5, Align::Left), "ab   ");
+        assert_eq!(fit("ab", 5, Align::Center), " ab  ");
+        assert_eq!(fit("ab", 5, Align::Right), "   ab");
+        assert_eq!(fit("абвгд", 3, Align::Left), "абв");
+    }
+
+    #[test]
+    fn fit_wide_and_combining() {
+        assert_eq!(fit("日本", 5, Align::Center), "日本 ");
+        assert_eq!(fit("日本", 3, Align::Left), "日 ");
+        assert_eq!(fit("e\u{301}e\u{301}", 3, Align::Right), " e\u{301}e\u{301}");
+    }
+
+    #[test]
+    fn fit_lines_fills_rectangle() {
+        assert_eq!(fit_lines("a\nb\nc", Size::new(2, 2), Align::Left), vec!["a ", "b "]);
+        assert_eq!(fit_lines("a", Size::new(2, 2), Align::Left), vec!["a ", "  "]);
+    }
+}
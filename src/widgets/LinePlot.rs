@@ -0,0 +1,211 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::chart::{draw_axes, format_value, Scale};
+use crate::widgets::layout::{text_width, Rect};
+use crate::widgets::style::{Color, Style};
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Биты точек шрифта Брайля в клетке: строка сверху вниз, столбец слева направо.
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+struct Series {
+    values: Vec<f64>,
+    style: Style,
+}
+
+/// Линейный график точками Брайля: в клетке 2×4 точки, поэтому линия вчетверо точнее строк.
+/// Рядов может быть несколько, каждый своим стилем; где линии пересекаются, клетка берёт стиль последнего.
+/// Значения ряда равномерно распределяются по ширине, подписи оси X задаются `set_x_range`.
+pub struct LinePlot {
+    series: Vec<Series>,
+    width: usize,
+    height: usize,
+    scale: Option<Scale>,
+    x_range: Option<(f64, f64)>,
+    axis_style: Themed<Style>,
+    dirty: bool,
+}
+
+impl LinePlot {
+    pub fn new(width: usize, height: usize) -> LinePlot {
+        LinePlot { series: Vec::new(), width, height, scale: None, x_range: None, axis_style: Themed::new(Style::default()), dirty: false }
+    }
+
+    /// Добавляет ряд и возвращает его номер для `set_series` и `push`.
+    pub fn add_series(&mut self, values: &[f64], style: Style) -> usize {
+        self.series.push(Series { values: values.to_vec(), style });
+        self.dirty = true;
+        self.series.len() - 1
+    }
+
+    pub fn series(mut self, values: &[f64], color: Color) -> LinePlot {
+        self.add_series(values, Style::new().fg(color));
+        self
+    }
+
+    pub fn set_series(&mut self, index: usize, values: &[f64]) {
+        if let Some(series) = self.series.get_mut(index) {
+            series.values = values.to_vec();
+            self.dirty = true;
+        }
+    }
+
+    /// Дописывает значение в ряд; если задан `limit`, старые значения сверх него отбрасываются.
+    pub fn push(&mut self, index: usize, value: f64, limit: Option<usize>) {
+        if let Some(series) = self.series.get_mut(index) {
+            series.values.push(value);
+            if let Some(limit) = limit {
+                let extra = series.values.len().saturating_sub(limit);
+                series.values.drain(..extra);
+            }
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_scale(&mut self, scale: Option<Scale>) {
+        self.scale = scale;
+        self.dirty = true;
+    }
+
+    /// Значения на краях оси X; по умолчанию — номера первой и последней точки.
+    pub fn set_x_range(&mut self, range: Option<(f64, f64)>) {
+        self.x_range = range;
+    }
+
+    pub fn set_axis_style(&mut self, style: Style) {
+        self.axis_style.set(style);
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.scale.unwrap_or_else(|| Scale::fit(self.series.iter().flat_map(|series| series.values.iter().copied()), false))
+    }
+
+    fn x_range(&self) -> (f64, f64) {
+        let points = self.series.iter().map(|series| series.values.len()).max().unwrap_or(0);
+        self.x_range.unwrap_or((0.0, points.saturating_sub(1) as f64))
+    }
+}
+
+/// Точки отрезка между двумя точками сетки (алгоритм Брезенхэма).
+fn line(from: (i64, i64), to: (i64, i64)) -> Vec<(i64, i64)> {
+    let (dx, dy) = ((to.0 - from.0).abs(), -(to.1 - from.1).abs());
+    let (sx, sy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    let (mut x, mut y, mut error) = (from.0, from.1, dx + dy);
+    let mut points = vec![(x, y)];
+    while (x, y) != to {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += sx;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += sy;
+        }
+        points.push((x, y));
+    }
+    points
+}
+
+impl Widget for LinePlot {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn accessible(&self) -> Accessible {
+        let scale = self.scale();
+        let value = format!("рядов {}, от {} до {}", self.series.len(), format_value(scale.min), format_value(scale.max));
+        Accessible::new(AccessRole::Chart, "").with_value(value)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Цвет каждого ряда задан при его добавлении, тема меняет только оси.
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.axis_style.theme(theme.style(Role::Border));
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        if area.height < 3 {
+            return;
+        }
+        let scale = self.scale();
+        let axis_style = self.axis_style.get();
+        // нижняя строка — под подписи оси X
+        let plot = draw_axes(canvas, Rect::new(area.x, area.y, area.width, area.height - 1), scale, axis_style);
+        if plot.width == 0 || plot.height == 0 {
+            return;
+        }
+        let (columns, rows) = (plot.width * 2, plot.height * 4);
+        let mut cells = vec![(0u32, Style::default()); plot.width * plot.height];
+        for series in &self.series {
+            let count = series.values.len();
+            let mut previous = None;
+            for (i, &value) in series.values.iter().enumerate() {
+                if !value.is_finite() {
+                    previous = None;
+                    continue;
+                }
+                let x = if count > 1 { (i * (columns - 1) + (count - 1) / 2) / (count - 1) } else { 0 };
+                let y = ((1.0 - scale.ratio(value)) * (rows - 1) as f64).round() as usize;
+                let point = (x as i64, y as i64);
+                for (x, y) in line(previous.unwrap_or(point), point) {
+                    let (x, y) = (x as usize, y as usize);
+                    let cell = &mut cells[y / 4 * plot.width + x / 2];
+                    cell.0 |= DOTS[y % 4][x % 2];
+                    cell.1 = series.style;
+                }
+                previous = Some(point);
+            }
+        }
+        for (i, &(bits, style)) in cells.iter().enumerate() {
+            if let Some(dots) = char::from_u32(0x2800 + bits).filter(|_| bits != 0) {
+                canvas.put_str(plot.x + i % plot.width, plot.y + i / plot.width, &dots.to_string(), style);
+            }
+        }
+        let (first, last) = self.x_range();
+        let (first, last) = (format_value(first), format_value(last));
+        let y = area.bottom() - 1;
+        canvas.put_str(plot.x, y, &first, axis_style);
+        if text_width(&first) + text_width(&last) < plot.width {
+            canvas.put_str(plot.right() - text_width(&last), y, &last, axis_style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render_plain;
+
+    #[test]
+    fn braille_line_between_points() {
+        assert_eq!(line((0, 0), (3, 1)), vec![(0, 0), (1, 0), (2, 1), (3, 1)]);
+        let plot = LinePlot::new(6, 4).series(&[0.0, 2.0, 4.0, 6.0, 8.0], Color::Red);
+        assert_eq!(plot.scale(), Scale::new(0.0, 8.0));
+        assert_eq!(render_plain(&plot, plot.size_hint()), "8|  ⢠⠊\n0|⡠⠊⠁ \n +----\n  0  4\n");
+    }
+
+    #[test]
+    fn x_labels_and_gaps() {
+        let mut plot = LinePlot::new(12, 4);
+        let series = plot.add_series(&[1.0, f64::NAN, 1.0], Style::default());
+        // NaN рвёт линию: между крайними точками ряда пусто
+        assert_eq!(render_plain(&plot, plot.size_hint()).lines().nth(1), Some("0.9|⠁      ⠈"));
+        plot.push(series, 1.0, Some(2));
+        plot.set_x_range(Some((0.0, 60.0)));
+        assert_eq!(render_plain(&plot, plot.size_hint()).lines().last(), Some("    0     60"));
+        assert_eq!(plot.accessible().value.as_deref(), Some("рядов 1, от 0.9 до 1.1"));
+    }
+}
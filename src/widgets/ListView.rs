@@ -0,0 +1,332 @@
+use std::cell::Cell;
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::event::{Event, KeyCode, MouseButton, MouseKind};
+use crate::widgets::layout::{fit, Align, Rect, Size};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Источник строк для `ListView`. Список спрашивает только видимые строки,
+/// поэтому элементы можно вычислять на лету, не держа их в памяти.
+pub trait ListModel {
+    fn len(&self) -> usize;
+
+    fn item(&self, index: usize) -> String;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: AsRef<str>> ListModel for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn item(&self, index: usize) -> String {
+        self[index].as_ref().to_owned()
+    }
+}
+
+/// Список, вычисляемый функцией от индекса.
+pub struct FnModel<F: Fn(usize) -> String> {
+    len: usize,
+    item: F,
+}
+
+impl<F: Fn(usize) -> String> FnModel<F> {
+    pub fn new(len: usize, item: F) -> FnModel<F> {
+        FnModel { len, item }
+    }
+}
+
+impl<F: Fn(usize) -> String> ListModel for FnModel<F> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn item(&self, index: usize) -> String {
+        (self.item)(index)
+    }
+}
+
+/// Прокручиваемый список с выделенной строкой. Рисуются только строки,
+/// попавшие в окно просмотра, так что размер списка на отрисовку не влияет.
+pub struct ListView {
+    model: Box<dyn ListModel>,
+    width: usize,
+    height: usize,
+    selected: Option<usize>,
+    /// Первая видимая строка; отрисовка подрезает её под окно, чтобы внизу не было пустоты.
+    offset: Cell<usize>,
+    /// Размер окна при последней отрисовке: раскладка может дать меньше, чем `height`.
+    viewport: Cell<Size>,
+    scrollbar: bool,
+    style: Themed<Style>,
+    selected_style: Themed<Style>,
+    on_select: Option<Box<dyn FnMut(usize)>>,
+}
+
+impl ListView {
+    pub fn new(model: impl ListModel + 'static, width: usize, height: usize) -> ListView {
+        let selected = (!model.is_empty()).then_some(0);
+        ListView {
+            model: Box::new(model),
+            width,
+            height,
+            selected,
+            offset: Cell::new(0),
+            viewport: Cell::new(Size::new(width, height)),
+            scrollbar: true,
+            style: Themed::new(Style::default()),
+            selected_style: Themed::new(Style::new().reverse()),
+            on_select: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.model.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.model.is_empty()
+    }
+
+    /// Заменяет содержимое; выделение и прокрутка сбрасываются к началу.
+    pub fn set_model(&mut self, model: impl ListModel + 'static) {
+        self.selected = (!model.is_empty()).then_some(0);
+        self.offset.set(0);
+        self.model = Box::new(model);
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn selected_item(&self) -> Option<String> {
+        self.selected.map(|i| self.model.item(i))
+    }
+
+    /// Первая видимая строка.
+    pub fn offset(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Сколько строк видно: по последней отрисовке.
+    fn rows(&self) -> usize {
+        self.viewport.get().height
+    }
+
+    /// Выделяет строку и прокручивает список так, чтобы она была видна.
+    pub fn select(&mut self, index: usize) {
+        if self.model.is_empty() {
+            return;
+        }
+        let index = index.min(self.model.len() - 1);
+        self.offset.set(scroll_to(self.offset.get(), index, self.rows()));
+        if self.selected != Some(index) {
+            self.selected = Some(index);
+            if let Some(on_select) = &mut self.on_select {
+                on_select(index);
+            }
+        }
+    }
+
+    /// Прокрутка без смены выделения.
+    pub fn scroll_by(&mut self, rows: isize) {
+        let max = self.model.len().saturating_sub(self.rows());
+        self.offset.set(self.offset.get().saturating_add_signed(rows).min(max));
+    }
+
+    pub fn set_scrollbar(&mut self, scrollbar: bool) {
+        self.scrollbar = scrollbar;
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style.set(style);
+    }
+
+    pub fn set_on_select(&mut self, on_select: impl FnMut(usize) + 'static) {
+        self.on_select = Some(Box::new(on_select));
+    }
+
+    fn has_scrollbar(&self, rows: usize) -> bool {
+        self.scrollbar && self.model.len() > rows
+    }
+}
+
+/// Смещение, при котором строка `index` попадает в окно из `rows` строк.
+fn scroll_to(offset: usize, index: usize, rows: usize) -> usize {
+    if index < offset {
+        index
+    } else if rows > 0 && index >= offset + rows {
+        index + 1 - rows
+    } else {
+        offset
+    }
+}
+
+impl Widget for ListView {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn accessible(&self) -> Accessible {
+        let accessible = Accessible::new(AccessRole::List, "");
+        match self.selected_item() {
+            Some(item) => accessible.with_value(format!("выбрано: {}", item)),
+            None => accessible.with_value(format!("элементов: {}", self.model.len())),
+        }
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        self.viewport.set(area.size());
+        let rows = area.height;
+        let len = self.model.len();
+        // раскладка могла дать больше строк, чем `height`: не оставляем пустоту внизу
+        let offset = self.offset.get().min(len.saturating_sub(rows));
+        self.offset.set(offset);
+        let scrollbar = self.has_scrollbar(rows) && area.width > 1;
+        let text_width = area.width - scrollbar as usize;
+
+        let (plain, selected) = (self.style.get(), self.selected_style.get());
+        canvas.fill(area, " ", plain);
+        for (y, index) in (area.y..area.bottom()).zip(offset..len) {
+            let style = if self.selected == Some(index) { selected } else { plain };
+            let line = fit(&self.model.item(index), text_width, Align::Left);
+            canvas.put_str(area.x, y, &line, style);
+        }
+
+        if scrollbar {
+            // ползунок пропорционален видимой части, но не короче одной строки
+            let x = area.right() - 1;
+            let thumb = (rows * rows / len).max(1);
+            let top = (offset * (rows - thumb)).checked_div(len - rows).unwrap_or(0);
+            for y in 0..rows {
+                let symbol = if (top..top + thumb).contains(&y) { "#" } else { "|" };
+                canvas.set(x, area.y + y, symbol, plain);
+            }
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Label));
+        self.selected_style.theme(theme.style(Role::ButtonFocused));
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Some(current) = self.selected else {
+            return false;
+        };
+        let page = self.rows().max(1);
+        let viewport = self.viewport.get();
+        let scrollbar = self.has_scrollbar(viewport.height) && viewport.width > 1;
+        match event {
+            Event::Key(key) if key.is(KeyCode::Up) => self.select(current.saturating_sub(1)),
+            Event::Key(key) if key.is(KeyCode::Down) => self.select(current + 1),
+            Event::Key(key) if key.is(KeyCode::PageUp) => self.select(current.saturating_sub(page)),
+            Event::Key(key) if key.is(KeyCode::PageDown) => self.select(current + page),
+            Event::Key(key) if key.is(KeyCode::Home) => self.select(0),
+            Event::Key(key) if key.is(KeyCode::End) => self.select(usize::MAX),
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseKind::ScrollUp => self.scroll_by(-3),
+                MouseKind::ScrollDown => self.scroll_by(3),
+                // щелчок по полосе прокрутки строку не выбирает
+                MouseKind::Press(MouseButton::Left) if scrollbar && mouse.x + 1 == viewport.width => return false,
+                MouseKind::Press(MouseButton::Left) if self.offset() + mouse.y < self.model.len() => {
+                    self.select(self.offset() + mouse.y)
+                }
+                _ => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::event::MouseEvent;
+    use crate::widgets::snapshot::render;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn renders_visible_rows_only() {
+        let requested = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&requested);
+        let model = FnModel::new(1_000_000, move |i| {
+            counter.set(counter.get() + 1);
+            format!("строка {}", i)
+        });
+        let mut list = ListView::new(model, 12, 3);
+        list.set_selected_style(Style::default());
+        list.select(999_999);
+        assert_eq!(list.offset(), 999_997);
+        requested.set(0);
+        assert_eq!(render(&list), "строка 9999|\nстрока 9999|\nстрока 9999#\n");
+        assert_eq!(requested.get(), 3);
+    }
+
+    #[test]
+    fn keyboard_and_mouse() {
+        let items: Vec<String> = (0..10).map(|i| format!("п{}", i)).collect();
+        let mut list = ListView::new(items, 6, 4);
+        list.set_scrollbar(false);
+        list.set_selected_style(Style::default());
+        list.handle_event(&Event::key(KeyCode::PageDown));
+        assert_eq!(list.selected(), Some(4));
+        assert_eq!(list.offset(), 1);
+        list.handle_event(&Event::key(KeyCode::End));
+        assert_eq!(list.selected_item().as_deref(), Some("п9"));
+        assert_eq!(render(&list), "п6    \nп7    \nп8    \nп9    \n");
+        list.handle_event(&Event::Mouse(MouseEvent { kind: MouseKind::ScrollUp, x: 0, y: 0 }));
+        assert_eq!(list.offset(), 3);
+        list.handle_event(&Event::click(0, 1));
+        assert_eq!(list.selected(), Some(4));
+    }
+
+    #[test]
+    fn scrollbar_thumb() {
+        let items: Vec<&str> = vec!["a"; 8];
+        let list = ListView::new(items, 3, 4);
+        assert_eq!(render(&list), "␛[7ma ␛[0m#\na #\na |\na |\n");
+    }
+
+    #[test]
+    fn shorter_area_than_height() {
+        let items: Vec<String> = (0..10).map(|i| format!("п{}", i)).collect();
+        let mut list = ListView::new(items, 4, 6);
+        list.set_selected_style(Style::default());
+        let mut canvas = Canvas::new(Size::new(4, 3));
+        list.render(&mut canvas, Rect::new(0, 0, 4, 3));
+        list.handle_event(&Event::key(KeyCode::PageDown));
+        assert_eq!(list.selected(), Some(3));
+        assert_eq!(list.offset(), 1, "выделенная строка осталась в окне из трёх строк");
+        list.handle_event(&Event::key(KeyCode::End));
+        assert_eq!(list.offset(), 7);
+
+        // окно выросло: отрисовка подрезает прокрутку, и щелчок попадает в ту строку, что видна
+        let mut canvas = Canvas::new(Size::new(4, 5));
+        list.render(&mut canvas, Rect::new(0, 0, 4, 5));
+        assert_eq!(list.offset(), 5);
+        list.handle_event(&Event::click(0, 1));
+        assert_eq!(list.selected(), Some(6));
+        assert!(!list.handle_event(&Event::click(3, 2)), "полоса прокрутки");
+        assert_eq!(list.selected(), Some(6));
+    }
+}
@@ -0,0 +1,958 @@
+use std::collections::HashMap;
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::border::BorderStyle;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::event::{Event, KeyCode, KeyEvent, MouseButton, MouseKind};
+use crate::widgets::layout::{text_width, Rect, Size};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Действия меню по именам команд. Пункт знает только имя команды, поэтому одно
+/// действие можно повесить на несколько пунктов и вызвать в обход меню.
+#[derive(Default)]
+pub struct Commands {
+    handlers: HashMap<String, Box<dyn FnMut()>>,
+}
+
+impl Commands {
+    pub fn new() -> Commands {
+        Commands::default()
+    }
+
+    pub fn register(&mut self, name: &str, handler: impl FnMut() + 'static) {
+        self.handlers.insert(name.to_owned(), Box::new(handler));
+    }
+
+    /// Выполняет команду; `false`, если такой нет.
+    pub fn run(&mut self, name: &str) -> bool {
+        match self.handlers.get_mut(name) {
+            Some(handler) => {
+                handler();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Буква-ускоритель надписи: `&Файл` → `ф`. Двойной `&&` — просто амперсанд.
+fn accelerator(label: &str) -> Option<char> {
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            match chars.next() {
+                Some('&') => continue,
+                Some(c) => return c.to_lowercase().next(),
+                None => return None,
+            }
+        }
+    }
+    None
+}
+
+/// Надпись без разметки ускорителя.
+fn plain(label: &str) -> String {
+    let mut text = String::new();
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => text.extend(chars.next()),
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+/// Рисует надпись, подчёркивая букву-ускоритель. Возвращает ширину.
+fn put_label(canvas: &mut Canvas, x: usize, y: usize, label: &str, style: Style) -> usize {
+    let mut column = x;
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        let (c, style) = match c {
+            '&' => match chars.next() {
+                Some('&') => ('&', style),
+                Some(c) => (c, style.underline()),
+                None => break,
+            },
+            c => (c, style),
+        };
+        column += canvas.put_str(column, y, &c.to_string(), style);
+    }
+    column - x
+}
+
+/// Нажата ли буква `c` с Alt или без модификаторов.
+fn letter(key: &KeyEvent) -> Option<char> {
+    match key.code {
+        KeyCode::Char(c) if !key.modifiers.ctrl => c.to_lowercase().next(),
+        _ => None,
+    }
+}
+
+enum Action {
+    Command(String),
+    Submenu(Menu),
+    Separator,
+}
+
+/// Пункт меню: команда, вложенное меню или разделитель.
+pub struct MenuItem {
+    label: String,
+    action: Action,
+    enabled: bool,
+    /// `Some` у пунктов-флажков.
+    checked: Option<bool>,
+    /// Подсказка справа, например `Ctrl+S`.
+    hint: String,
+}
+
+impl MenuItem {
+    /// Пункт, выполняющий команду `command`. `&` в надписи отмечает букву-ускоритель.
+    pub fn new(label: &str, command: &str) -> MenuItem {
+        MenuItem::with_action(label, Action::Command(command.to_owned()))
+    }
+
+    /// Пункт, открывающий вложенное меню; надпись берётся из его заголовка.
+    pub fn submenu(menu: Menu) -> MenuItem {
+        MenuItem::with_action(&menu.title.clone(), Action::Submenu(menu))
+    }
+
+    pub fn separator() -> MenuItem {
+        MenuItem::with_action("", Action::Separator)
+    }
+
+    fn with_action(label: &str, action: Action) -> MenuItem {
+        MenuItem { label: label.to_owned(), action, enabled: true, checked: None, hint: String::new() }
+    }
+
+    pub fn disabled(mut self) -> MenuItem {
+        self.enabled = false;
+        self
+    }
+
+    /// Делает пункт флажком; выбор пункта переключает его перед вызовом команды.
+    pub fn checkable(mut self, checked: bool) -> MenuItem {
+        self.checked = Some(checked);
+        self
+    }
+
+    pub fn hint(mut self, hint: &str) -> MenuItem {
+        self.hint = hint.to_owned();
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked == Some(true)
+    }
+
+    fn is_selectable(&self) -> bool {
+        self.enabled && !matches!(self.action, Action::Separator)
+    }
+
+    /// Текст справа: подсказка или стрелка вложенного меню.
+    fn right(&self) -> &str {
+        match self.action {
+            Action::Submenu(_) => ">",
+            _ => &self.hint,
+        }
+    }
+}
+
+/// Список пунктов с заголовком: выпадающее меню строки меню, вложенное или контекстное.
+pub struct Menu {
+    title: String,
+    items: Vec<MenuItem>,
+}
+
+impl Menu {
+    pub fn new(title: &str) -> Menu {
+        Menu { title: title.to_owned(), items: Vec::new() }
+    }
+
+    pub fn item(mut self, item: MenuItem) -> Menu {
+        self.add_item(item);
+        self
+    }
+
+    pub fn add_item(&mut self, item: MenuItem) {
+        self.items.push(item);
+    }
+
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    pub fn items_mut(&mut self) -> &mut [MenuItem] {
+        &mut self.items
+    }
+
+    /// Состояние флажка с командой `command` здесь или во вложенных меню.
+    pub fn is_checked(&self, command: &str) -> Option<bool> {
+        self.items.iter().find_map(|item| match &item.action {
+            Action::Command(name) if name == command => item.checked,
+            Action::Submenu(menu) => menu.is_checked(command),
+            _ => None,
+        })
+    }
+
+    fn first_selectable(&self) -> Option<usize> {
+        self.items.iter().position(MenuItem::is_selectable)
+    }
+
+    /// Следующий доступный пункт после `from` по кругу.
+    fn step(&self, from: usize, forward: bool) -> usize {
+        let count = self.items.len();
+        (1..=count)
+            .map(|offset| if forward { (from + offset) % count } else { (from + count - offset) % count })
+            .find(|&i| self.items[i].is_selectable())
+            .unwrap_or(from)
+    }
+
+    fn has_checks(&self) -> bool {
+        self.items.iter().any(|item| item.checked.is_some())
+    }
+
+    /// Размер выпадающего окна вместе с рамкой.
+    fn popup_size(&self) -> Size {
+        let checks = if self.has_checks() { 4 } else { 0 };
+        let left = self.items.iter().map(|item| text_width(&plain(&item.label))).max().unwrap_or(0);
+        let right = self.items.iter().map(|item| text_width(item.right())).max().unwrap_or(0);
+        let inner = 1 + checks + left + if right > 0 { 2 + right } else { 0 } + 1;
+        Size::new(inner + 2, self.items.len() + 2)
+    }
+
+    /// Размер окна со всеми вложенными меню, раскрытыми вправо.
+    fn extent(&self) -> Size {
+        let own = self.popup_size();
+        let mut extent = own;
+        for (i, item) in self.items.iter().enumerate() {
+            if let Action::Submenu(menu) = &item.action {
+                let nested = menu.extent();
+                extent.width = extent.width.max(own.width + nested.width);
+                extent.height = extent.height.max(i + nested.height);
+            }
+        }
+        extent
+    }
+
+    fn render_popup(&self, canvas: &mut Canvas, x: usize, y: usize, highlighted: usize, styles: &MenuStyles) {
+        let size = self.popup_size();
+        let (border, normal) = (styles.border.get(), styles.normal.get());
+        canvas.fill(Rect::new(x, y, size.width, size.height), " ", normal);
+        canvas.draw_box(Rect::new(x, y, size.width, size.height), &border, normal);
+        let inner = size.width - 2;
+        let checks = self.has_checks();
+        for (i, item) in self.items.iter().enumerate() {
+            let row = y + 1 + i;
+            if let Action::Separator = item.action {
+                let line = BorderStyle::line(border.left_tee, border.horizontal, inner, border.right_tee);
+                canvas.put_border(x, row, &line, normal);
+                continue;
+            }
+            let style = if i == highlighted {
+                styles.selected.get()
+            } else if item.enabled {
+                normal
+            } else {
+                styles.disabled.get()
+            };
+            canvas.fill(Rect::new(x + 1, row, inner, 1), " ", style);
+            let mut column = x + 2;
+            if checks {
+                let mark = match item.checked {
+                    Some(true) => "[x]",
+                    Some(false) => "[ ]",
+                    None => "",
+                };
+                canvas.put_str(column, row, mark, style);
+                column += 4;
+            }
+            put_label(canvas, column, row, &item.label, style);
+            let right = item.right();
+            canvas.put_str(x + size.width - 2 - text_width(right), row, right, style);
+        }
+    }
+}
+
+/// Оформление строки меню и всплывающих меню.
+struct MenuStyles {
+    normal: Themed<Style>,
+    selected: Themed<Style>,
+    disabled: Themed<Style>,
+    border: Themed<BorderStyle>,
+}
+
+impl MenuStyles {
+    /// Пункты оформлены как кнопки, выделенный — как кнопка в фокусе, рамка — как у окон.
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.normal.theme(theme.style(Role::Button));
+        self.selected.theme(theme.style(Role::ButtonFocused));
+        self.disabled.theme(theme.style(Role::Button).dim());
+        self.border.theme(theme.window_border);
+    }
+}
+
+impl Default for MenuStyles {
+    fn default() -> MenuStyles {
+        MenuStyles {
+            normal: Themed::new(Style::default()),
+            selected: Themed::new(Style::new().reverse()),
+            disabled: Themed::new(Style::new().dim()),
+            border: Themed::new(BorderStyle::ASCII),
+        }
+    }
+}
+
+/// Открытые уровни меню: само меню, левый верхний угол его окна и выделенный пункт.
+fn levels<'a>(root: &'a Menu, path: &[usize], x: usize, y: usize) -> Vec<(&'a Menu, usize, usize, usize)> {
+    let mut levels = Vec::new();
+    let (mut menu, mut x, mut y) = (root, x, y);
+    for (depth, &index) in path.iter().enumerate() {
+        levels.push((menu, x, y, index));
+        if depth + 1 == path.len() {
+            break;
+        }
+        match &menu.items[index].action {
+            Action::Submenu(submenu) => {
+                x += menu.popup_size().width;
+                y += index;
+                menu = submenu;
+            }
+            _ => break,
+        }
+    }
+    levels
+}
+
+fn menu_at<'a>(root: &'a Menu, path: &[usize]) -> Option<&'a Menu> {
+    path.iter().try_fold(root, |menu, &index| match &menu.items.get(index)?.action {
+        Action::Submenu(submenu) => Some(submenu),
+        _ => None,
+    })
+}
+
+fn menu_at_mut<'a>(root: &'a mut Menu, path: &[usize]) -> Option<&'a mut Menu> {
+    path.iter().try_fold(root, |menu, &index| match &mut menu.items.get_mut(index)?.action {
+        Action::Submenu(submenu) => Some(submenu),
+        _ => None,
+    })
+}
+
+/// Чем закончилась обработка клавиши в открытом меню.
+enum Outcome {
+    Unhandled,
+    Handled,
+    /// Выбран пункт с командой.
+    Run(String),
+    /// Переход к соседнему меню строки меню.
+    Next,
+    Prev,
+    /// Меню закрыто с верхнего уровня.
+    Close,
+}
+
+/// Выбирает выделенный пункт: раскрывает вложенное меню или возвращает команду.
+fn activate(root: &mut Menu, path: &mut Vec<usize>) -> Outcome {
+    let Some((&index, parents)) = path.split_last() else {
+        return Outcome::Unhandled;
+    };
+    let Some(item) = menu_at_mut(root, parents).and_then(|menu| menu.items.get_mut(index)) else {
+        return Outcome::Unhandled;
+    };
+    if !item.enabled {
+        return Outcome::Handled;
+    }
+    match &item.action {
+        Action::Submenu(submenu) => {
+            path.extend(submenu.first_selectable());
+            Outcome::Handled
+        }
+        Action::Command(name) => {
+            if let Some(checked) = &mut item.checked {
+                *checked = !*checked;
+            }
+            Outcome::Run(name.clone())
+        }
+        Action::Separator => Outcome::Handled,
+    }
+}
+
+/// Клавиши внутри открытого меню: стрелки, Enter, Esc и буквы-ускорители пунктов.
+fn navigate(root: &mut Menu, path: &mut Vec<usize>, key: &KeyEvent) -> Outcome {
+    let Some((&current, parents)) = path.split_last() else {
+        return Outcome::Unhandled;
+    };
+    let Some(menu) = menu_at(root, parents) else {
+        return Outcome::Unhandled;
+    };
+    match key.code {
+        KeyCode::Up | KeyCode::Down => {
+            let next = menu.step(current, key.code == KeyCode::Down);
+            path.pop();
+            path.push(next);
+            Outcome::Handled
+        }
+        KeyCode::Right if matches!(menu.items[current].action, Action::Submenu(_)) => activate(root, path),
+        KeyCode::Right => Outcome::Next,
+        KeyCode::Left | KeyCode::Esc if path.len() > 1 => {
+            path.pop();
+            Outcome::Handled
+        }
+        KeyCode::Left => Outcome::Prev,
+        KeyCode::Esc => Outcome::Close,
+        KeyCode::Enter | KeyCode::Char(' ') => activate(root, path),
+        _ => {
+            let Some(c) = letter(key) else {
+                return Outcome::Unhandled;
+            };
+            let found = menu.items.iter().position(|item| item.is_selectable() && accelerator(&item.label) == Some(c));
+            match found {
+                Some(index) => {
+                    path.pop();
+                    path.push(index);
+                    activate(root, path)
+                }
+                None => Outcome::Unhandled,
+            }
+        }
+    }
+}
+
+/// Пункт под точкой `x, y` среди открытых уровней: (уровень, номер пункта).
+fn hit(root: &Menu, path: &[usize], origin: (usize, usize), x: usize, y: usize) -> Option<(usize, usize)> {
+    let levels = levels(root, path, origin.0, origin.1);
+    levels.iter().enumerate().rev().find_map(|(depth, (menu, left, top, _))| {
+        let size = menu.popup_size();
+        let inside = Rect::new(left + 1, top + 1, size.width - 2, size.height - 2).contains(x, y);
+        inside.then(|| (depth, y - top - 1))
+    })
+}
+
+/// Щелчок по пункту: выделяет его и выбирает.
+fn click(root: &mut Menu, path: &mut Vec<usize>, depth: usize, index: usize) -> Outcome {
+    let selectable = menu_at(root, &path[..depth]).is_some_and(|menu| menu.items[index].is_selectable());
+    if !selectable {
+        return Outcome::Handled;
+    }
+    path.truncate(depth);
+    path.push(index);
+    activate(root, path)
+}
+
+/// Строка меню `Файл  Правка  Вид` с выпадающими меню. Обычно ставится в окно
+/// через `Window::set_menu_bar`. Alt с буквой-ускорителем или F10 открывают меню
+/// откуда угодно, выбранные пункты вызывают команды из `commands_mut`.
+pub struct MenuBar {
+    menus: Vec<Menu>,
+    selected: usize,
+    /// Выделенные пункты по открытым уровням; пусто — меню закрыто.
+    path: Vec<usize>,
+    /// Строка выбрана: по ней ходят стрелками, даже если меню не раскрыто.
+    active: bool,
+    commands: Commands,
+    styles: MenuStyles,
+}
+
+impl MenuBar {
+    pub fn new() -> MenuBar {
+        MenuBar {
+            menus: Vec::new(),
+            selected: 0,
+            path: Vec::new(),
+            active: false,
+            commands: Commands::new(),
+            styles: MenuStyles::default(),
+        }
+    }
+
+    pub fn menu(mut self, menu: Menu) -> MenuBar {
+        self.add_menu(menu);
+        self
+    }
+
+    pub fn add_menu(&mut self, menu: Menu) {
+        self.menus.push(menu);
+    }
+
+    pub fn menus_mut(&mut self) -> &mut [Menu] {
+        &mut self.menus
+    }
+
+    pub fn commands_mut(&mut self) -> &mut Commands {
+        &mut self.commands
+    }
+
+    /// Регистрирует действие команды.
+    pub fn on_command(&mut self, name: &str, handler: impl FnMut() + 'static) {
+        self.commands.register(name, handler);
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.path.is_empty()
+    }
+
+    /// Открытое меню и его выделенный пункт.
+    pub fn open_menu(&self) -> Option<&Menu> {
+        self.is_open().then(|| &self.menus[self.selected])
+    }
+
+    pub fn is_checked(&self, command: &str) -> bool {
+        self.menus.iter().find_map(|menu| menu.is_checked(command)) == Some(true)
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.styles.normal.set(style);
+    }
+
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.styles.selected.set(style);
+    }
+
+    /// Рамка раскрытых меню.
+    pub fn set_border(&mut self, border: BorderStyle) {
+        self.styles.border.set(border);
+    }
+
+    /// Открывает меню с номером `index` и выделяет его первый доступный пункт.
+    pub fn open(&mut self, index: usize) {
+        if let Some(menu) = self.menus.get(index) {
+            self.selected = index;
+            self.path = menu.first_selectable().into_iter().collect();
+            self.active = true;
+        }
+    }
+
+    /// Закрывает меню и снимает выбор со строки.
+    pub fn close(&mut self) {
+        self.path.clear();
+        self.active = false;
+    }
+
+    /// Левые края заголовков и их ширина.
+    fn titles(&self) -> Vec<(usize, usize)> {
+        let mut x = 0;
+        self.menus
+            .iter()
+            .map(|menu| {
+                let width = text_width(&plain(&menu.title)) + 2;
+                x += width;
+                (x - width, width)
+            })
+            .collect()
+    }
+
+    fn apply(&mut self, outcome: Outcome) -> bool {
+        let count = self.menus.len();
+        match outcome {
+            Outcome::Unhandled => false,
+            Outcome::Handled => true,
+            Outcome::Run(command) => {
+                self.close();
+                self.commands.run(&command);
+                true
+            }
+            Outcome::Next => {
+                self.open((self.selected + 1) % count);
+                true
+            }
+            Outcome::Prev => {
+                self.open((self.selected + count - 1) % count);
+                true
+            }
+            Outcome::Close => {
+                self.path.clear();
+                true
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if !self.active {
+            return false;
+        }
+        if self.is_open() {
+            let outcome = navigate(&mut self.menus[self.selected], &mut self.path, key);
+            return self.apply(outcome);
+        }
+        let count = self.menus.len();
+        match key.code {
+            KeyCode::Left => self.selected = (self.selected + count - 1) % count,
+            KeyCode::Right => self.selected = (self.selected + 1) % count,
+            KeyCode::Enter | KeyCode::Down | KeyCode::Char(' ') => self.open(self.selected),
+            KeyCode::Esc => self.close(),
+            _ => {
+                let found = letter(key).and_then(|c| self.menus.iter().position(|menu| accelerator(&menu.title) == Some(c)));
+                match found {
+                    Some(index) => self.open(index),
+                    None => return false,
+                }
+            }
+        }
+        true
+    }
+
+    fn handle_click(&mut self, x: usize, y: usize) -> bool {
+        if y == 0 {
+            let title = self.titles().into_iter().position(|(left, width)| (left..left + width).contains(&x));
+            match title {
+                Some(index) if self.is_open() && index == self.selected => self.close(),
+                Some(index) => self.open(index),
+                None => self.close(),
+            }
+            return true;
+        }
+        if !self.is_open() {
+            return false;
+        }
+        let origin = (self.titles()[self.selected].0, 1);
+        match hit(&self.menus[self.selected], &self.path, origin, x, y) {
+            Some((depth, index)) => {
+                let outcome = click(&mut self.menus[self.selected], &mut self.path, depth, index);
+                self.apply(outcome)
+            }
+            None => {
+                self.close();
+                true
+            }
+        }
+    }
+}
+
+impl Default for MenuBar {
+    fn default() -> MenuBar {
+        MenuBar::new()
+    }
+}
+
+impl Widget for MenuBar {
+    fn width(&self) -> usize {
+        self.titles().iter().map(|(_, width)| width).sum()
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+
+    fn accessible(&self) -> Accessible {
+        let titles: Vec<String> = self.menus.iter().map(|menu| plain(&menu.title)).collect();
+        Accessible::new(AccessRole::MenuBar, "").with_value(titles.join(", "))
+    }
+
+    /// Строка заголовков занимает верхнюю строку `area`, открытые меню рисуются ниже.
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let normal = self.styles.normal.get();
+        canvas.fill(Rect::new(area.x, area.y, area.width, 1), " ", normal);
+        let titles = self.titles();
+        for (i, (menu, (left, _))) in self.menus.iter().zip(&titles).enumerate() {
+            let style = if self.active && i == self.selected { self.styles.selected.get() } else { normal };
+            let x = area.x + left;
+            canvas.put_str(x, area.y, " ", style);
+            let width = put_label(canvas, x + 1, area.y, &menu.title, style);
+            canvas.put_str(x + 1 + width, area.y, " ", style);
+        }
+        if self.is_open() {
+            let origin = (area.x + titles[self.selected].0, area.y + 1);
+            for (menu, x, y, index) in levels(&self.menus[self.selected], &self.path, origin.0, origin.1) {
+                menu.render_popup(canvas, x, y, index, &self.styles);
+            }
+        }
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.styles.apply_theme(theme);
+    }
+
+    /// Фокус держится, пока строка выбрана; закрыв меню, она отдаёт его обратно.
+    fn is_focusable(&self) -> bool {
+        self.active
+    }
+
+    /// В строку меню попадают по Alt, F10 или мышью, а не по Tab.
+    fn is_tab_stop(&self) -> bool {
+        false
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        if focused {
+            self.active = !self.menus.is_empty();
+        } else {
+            self.close();
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Mouse(mouse) if mouse.kind == MouseKind::Press(MouseButton::Left) => self.handle_click(mouse.x, mouse.y),
+            _ => false,
+        }
+    }
+
+    fn handle_shortcut(&mut self, key: &KeyEvent) -> bool {
+        if key.code == KeyCode::F(10) {
+            self.open(0);
+            return !self.menus.is_empty();
+        }
+        if !key.modifiers.alt {
+            return false;
+        }
+        let found = letter(key).and_then(|c| self.menus.iter().position(|menu| accelerator(&menu.title) == Some(c)));
+        if let Some(index) = found {
+            self.open(index);
+        }
+        found.is_some()
+    }
+}
+
+/// Всплывающее (контекстное) меню: открыто сразу после создания и закрывается
+/// выбором пункта или Esc. Вложенные меню раскрываются вправо в пределах его размера.
+pub struct ContextMenu {
+    menu: Menu,
+    path: Vec<usize>,
+    commands: Commands,
+    styles: MenuStyles,
+}
+
+impl ContextMenu {
+    pub fn new(menu: Menu) -> ContextMenu {
+        let mut context = ContextMenu { menu, path: Vec::new(), commands: Commands::new(), styles: MenuStyles::default() };
+        context.open();
+        context
+    }
+
+    pub fn open(&mut self) {
+        self.path = self.menu.first_selectable().into_iter().collect();
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.path.is_empty()
+    }
+
+    pub fn commands_mut(&mut self) -> &mut Commands {
+        &mut self.commands
+    }
+
+    pub fn on_command(&mut self, name: &str, handler: impl FnMut() + 'static) {
+        self.commands.register(name, handler);
+    }
+
+    pub fn is_checked(&self, command: &str) -> bool {
+        self.menu.is_checked(command) == Some(true)
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.styles.normal.set(style);
+    }
+
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.styles.selected.set(style);
+    }
+
+    pub fn set_border(&mut self, border: BorderStyle) {
+        self.styles.border.set(border);
+    }
+
+    fn apply(&mut self, outcome: Outcome) -> bool {
+        match outcome {
+            Outcome::Unhandled => false,
+            Outcome::Handled | Outcome::Next | Outcome::Prev => true,
+            Outcome::Run(command) => {
+                self.path.clear();
+                self.commands.run(&command);
+                true
+            }
+            Outcome::Close => {
+                self.path.clear();
+                true
+            }
+        }
+    }
+}
+
+impl Widget for ContextMenu {
+    fn width(&self) -> usize {
+        self.menu.extent().width
+    }
+
+    fn height(&self) -> usize {
+        self.menu.extent().height
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::Menu, &plain(&self.menu.title))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        for (menu, x, y, index) in levels(&self.menu, &self.path, area.x, area.y) {
+            menu.render_popup(canvas, x, y, index, &self.styles);
+        }
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.styles.apply_theme(theme);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.is_open()
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let outcome = match event {
+            Event::Key(key) => navigate(&mut self.menu, &mut self.path, key),
+            Event::Mouse(mouse) if mouse.kind == MouseKind::Press(MouseButton::Left) && self.is_open() => {
+                match hit(&self.menu, &self.path, (0, 0), mouse.x, mouse.y) {
+                    Some((depth, index)) => click(&mut self.menu, &mut self.path, depth, index),
+                    None => Outcome::Close,
+                }
+            }
+            _ => Outcome::Unhandled,
+        };
+        self.apply(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render_plain;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn menu_bar(log: &Rc<RefCell<Vec<&'static str>>>) -> MenuBar {
+        let mut bar = MenuBar::new()
+            .menu(
+                Menu::new("&Файл")
+                    .item(MenuItem::new("&Открыть", "open").hint("Ctrl+O"))
+                    .item(MenuItem::new("&Сохранить", "save").disabled())
+                    .item(MenuItem::separator())
+                    .item(MenuItem::submenu(Menu::new("&Недавние").item(MenuItem::new("&отчёт.txt", "recent"))))
+                    .item(MenuItem::new("&Выход", "quit")),
+            )
+            .menu(Menu::new("&Вид").item(MenuItem::new("&Строка состояния", "status").checkable(true)));
+        for command in ["open", "save", "recent", "quit", "status"] {
+            let log = Rc::clone(log);
+            bar.on_command(command, move || log.borrow_mut().push(command));
+        }
+        bar
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::key(code)
+    }
+
+    #[test]
+    fn dropdown_with_separator_and_submenu() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bar = menu_bar(&log);
+        assert_eq!(render_plain(&bar, Size::new(12, 1)), " Файл  Вид  \n");
+        assert!(bar.handle_shortcut(&KeyEvent::alt(KeyCode::Char('ф'))));
+        bar.handle_event(&key(KeyCode::Up));
+        bar.handle_event(&key(KeyCode::Up));
+        bar.handle_event(&key(KeyCode::Right));
+        assert_eq!(
+            render_plain(&bar, Size::new(34, 8)),
+            " Файл  Вид                        \n\
+             +-------------------+             \n\
+             | Открыть    Ctrl+O |             \n\
+             | Сохранить         |             \n\
+             +-------------------++-----------+\n\
+             | Недавние        > || отчёт.txt |\n\
+             | Выход             |+-----------+\n\
+             +-------------------+             \n"
+        );
+        bar.handle_event(&key(KeyCode::Left));
+        assert_eq!(bar.path, vec![3]);
+        bar.handle_event(&key(KeyCode::Enter));
+        bar.handle_event(&key(KeyCode::Enter));
+        assert_eq!(*log.borrow(), vec!["recent"]);
+        assert!(!bar.is_open());
+    }
+
+    #[test]
+    fn keyboard_skips_disabled_and_accelerators_run_commands() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bar = menu_bar(&log);
+        bar.set_focused(true);
+        bar.handle_event(&key(KeyCode::Enter));
+        bar.handle_event(&key(KeyCode::Down));
+        // «Сохранить» недоступен, разделитель пропускается
+        assert_eq!(bar.path, vec![3]);
+        assert!(!bar.handle_event(&key(KeyCode::Char('с'))));
+        bar.handle_event(&key(KeyCode::Char('в')));
+        assert_eq!(*log.borrow(), vec!["quit"]);
+        assert!(!bar.is_focusable());
+        assert!(!bar.handle_event(&key(KeyCode::Right)));
+
+        assert!(!bar.handle_shortcut(&KeyEvent::new(KeyCode::Char('в'))));
+        assert!(bar.handle_shortcut(&KeyEvent::alt(KeyCode::Char('В'))));
+        bar.handle_event(&key(KeyCode::Enter));
+        assert!(!bar.is_checked("status"));
+        assert_eq!(*log.borrow(), vec!["quit", "status"]);
+
+        assert!(bar.handle_shortcut(&KeyEvent::new(KeyCode::F(10))));
+        bar.handle_event(&key(KeyCode::Left));
+        assert_eq!(bar.open_menu().map(|menu| menu.title.as_str()), Some("&Вид"));
+        bar.handle_event(&key(KeyCode::Esc));
+        assert!(!bar.is_open() && bar.is_focusable());
+        bar.handle_event(&key(KeyCode::Esc));
+        assert!(!bar.is_focusable());
+    }
+
+    #[test]
+    fn mouse_opens_and_picks() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bar = menu_bar(&log);
+        assert!(bar.handle_event(&Event::click(7, 0)));
+        assert_eq!(bar.open_menu().map(|menu| menu.title.as_str()), Some("&Вид"));
+        bar.handle_event(&Event::click(9, 2));
+        assert_eq!(*log.borrow(), vec!["status"]);
+        assert!(!bar.is_checked("status"));
+        bar.handle_event(&Event::click(1, 0));
+        bar.handle_event(&Event::click(3, 3));
+        assert!(bar.is_open(), "недоступный пункт не закрывает меню");
+        bar.handle_event(&Event::click(30, 8));
+        assert!(!bar.is_open());
+    }
+
+    #[test]
+    fn theme_reaches_menus_in_a_window() {
+        let theme = Theme::light();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bar = menu_bar(&log);
+        bar.set_selected_style(Style::new().underline());
+        bar.open(0);
+        let mut window = crate::widgets::Window::new("");
+        window.set_menu_bar(bar);
+        window.apply_theme(&theme);
+        let Some(bar) = window.children().into_iter().next() else { panic!() };
+        let mut canvas = Canvas::new(Size::new(24, 8));
+        bar.render(&mut canvas, Rect::new(0, 0, 24, 8));
+        let cell = |x, y| canvas.get(x, y).unwrap();
+        assert_eq!((cell(0, 1).symbol.as_str(), cell(0, 1).style), ("┌", theme.style(Role::Button)));
+        assert_eq!(cell(8, 0).style, theme.style(Role::Button));
+        assert_eq!(cell(1, 0).style, Style::new().underline(), "своя настройка остаётся");
+        assert_eq!(cell(4, 3).style, theme.style(Role::Button).dim(), "недоступный пункт");
+    }
+
+    #[test]
+    fn context_menu_closes_after_choice() {
+        let picked = Rc::new(RefCell::new(None));
+        let mut menu = ContextMenu::new(Menu::new("Правка").item(MenuItem::new("&Копировать", "copy")).item(MenuItem::new("&Вставить", "paste")));
+        let slot = Rc::clone(&picked);
+        menu.on_command("paste", move || *slot.borrow_mut() = Some("paste"));
+        assert_eq!(menu.size_hint(), Size::new(14, 4));
+        menu.handle_event(&key(KeyCode::Char('в')));
+        assert_eq!(*picked.borrow(), Some("paste"));
+        assert!(!menu.is_open());
+        assert!(!menu.is_focusable());
+    }
+}
@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::layout::{text_width, Rect};
+use crate::widgets::style::{Color, Style};
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Полоса выполнения задачи с известным объёмом: `[#####-----]  50% 0:12`.
+/// Справа — процент и, если включено, оценка оставшегося времени.
+pub struct ProgressBar {
+    width: usize,
+    done: u64,
+    total: u64,
+    started: Instant,
+    show_percent: bool,
+    show_eta: bool,
+    fill: char,
+    empty: char,
+    style: Themed<Style>,
+    fill_style: Themed<Style>,
+    dirty: bool,
+}
+
+impl ProgressBar {
+    pub fn new(width: usize, total: u64) -> ProgressBar {
+        ProgressBar {
+            width,
+            done: 0,
+            total,
+            started: Instant::now(),
+            show_percent: true,
+            show_eta: false,
+            fill: '#',
+            empty: '-',
+            style: Themed::new(Style::default()),
+            fill_style: Themed::new(Style::new().fg(Color::Green)),
+            dirty: false,
+        }
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn set_done(&mut self, done: u64) {
+        self.done = done.min(self.total);
+        self.dirty = true;
+    }
+
+    pub fn advance(&mut self, by: u64) {
+        self.set_done(self.done.saturating_add(by));
+    }
+
+    pub fn set_total(&mut self, total: u64) {
+        self.total = total;
+        self.done = self.done.min(total);
+        self.dirty = true;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.done >= self.total
+    }
+
+    /// Доля выполненного от 0 до 1; задача нулевого объёма считается выполненной.
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.done as f64 / self.total as f64
+        }
+    }
+
+    pub fn set_show_percent(&mut self, show: bool) {
+        self.show_percent = show;
+    }
+
+    pub fn set_show_eta(&mut self, show: bool) {
+        self.show_eta = show;
+    }
+
+    /// Момент начала работы, от которого считается оставшееся время.
+    pub fn set_started(&mut self, started: Instant) {
+        self.started = started;
+    }
+
+    /// Символы заполненной и пустой части полосы.
+    pub fn set_chars(&mut self, fill: char, empty: char) {
+        self.fill = fill;
+        self.empty = empty;
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_fill_style(&mut self, style: Style) {
+        self.fill_style.set(style);
+    }
+
+    /// Оставшееся время при той же скорости, что и до сих пор; `None`, пока ничего не сделано.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.done == 0 {
+            return None;
+        }
+        let elapsed = self.started.elapsed().as_secs_f64();
+        Some(Duration::from_secs_f64(elapsed * (self.total - self.done) as f64 / self.done as f64))
+    }
+
+    /// Текст справа от полосы.
+    fn status(&self) -> String {
+        let mut parts = Vec::new();
+        if self.show_percent {
+            parts.push(format!("{:>3}%", (self.ratio() * 100.0).floor() as u64));
+        }
+        if self.show_eta {
+            parts.push(match self.eta() {
+                Some(eta) => format_duration(eta),
+                None => "-:--".to_owned(),
+            });
+        }
+        parts.join(" ")
+    }
+}
+
+/// `м:сс` или `ч:мм:сс` для долгих задач.
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds >= 3600 {
+        format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+impl Widget for ProgressBar {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::ProgressBar, "").with_value(format!("{}%", (self.ratio() * 100.0).floor() as u64))
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Label));
+        self.fill_style.theme(theme.style(Role::Accent));
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let status = self.status();
+        let status_width = if status.is_empty() { 0 } else { text_width(&status) + 1 };
+        // скобки полосы и текст справа; полоса получает то, что осталось
+        let bar = area.width.saturating_sub(status_width + 2);
+        let filled = (bar as f64 * self.ratio()).floor() as usize;
+        let style = self.style.get();
+        let mut x = area.x;
+        x += canvas.put_str(x, area.y, "[", style);
+        x += canvas.put_str(x, area.y, &self.fill.to_string().repeat(filled), self.fill_style.get());
+        x += canvas.put_str(x, area.y, &self.empty.to_string().repeat(bar - filled), style);
+        x += canvas.put_str(x, area.y, "]", style);
+        if !status.is_empty() {
+            canvas.put_str(x + 1, area.y, &status, style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render;
+
+    #[test]
+    fn bar_and_percent() {
+        let mut bar = ProgressBar::new(20, 8);
+        bar.set_fill_style(Style::default());
+        assert_eq!(render(&bar), "[-------------]   0%\n");
+        bar.advance(3);
+        assert_eq!(render(&bar), "[####---------]  37%\n");
+        bar.advance(10);
+        assert!(bar.is_finished());
+        assert_eq!(render(&bar), "[#############] 100%\n");
+        bar.set_show_percent(false);
+        assert_eq!(render(&bar), "[##################]\n");
+    }
+
+    #[test]
+    fn eta_from_elapsed_time() {
+        let mut bar = ProgressBar::new(24, 100);
+        bar.set_fill_style(Style::default());
+        bar.set_show_eta(true);
+        assert_eq!(render(&bar), "[------------]   0% -:--\n");
+        bar.set_started(Instant::now() - Duration::from_secs(30));
+        bar.set_done(25);
+        let eta = bar.eta().unwrap().as_secs();
+        assert!((89..=91).contains(&eta), "{}", eta);
+        assert_eq!(format_duration(Duration::from_secs(90)), "1:30");
+        assert_eq!(format_duration(Duration::from_secs(3725)), "1:02:05");
+    }
+}
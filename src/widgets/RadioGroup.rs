@@ -0,0 +1,202 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::border::BorderStyle;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::Value;
+use crate::widgets::event::{Event, KeyCode, MouseButton, MouseKind};
+use crate::widgets::layout::{text_width, Rect};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Группа взаимоисключающих вариантов в рамке с заголовком:
+/// выбран может быть только один. Стрелки выбирают соседний вариант.
+pub struct RadioGroup {
+    title: String,
+    options: Vec<String>,
+    selected: Option<usize>,
+    border: Themed<BorderStyle>,
+    border_style: Themed<Style>,
+    style: Themed<Style>,
+    focus_style: Themed<Style>,
+    focused: bool,
+    on_change: Option<Box<dyn FnMut(usize)>>,
+}
+
+impl RadioGroup {
+    pub fn new(title: &str, options: &[&str]) -> RadioGroup {
+        RadioGroup {
+            title: title.to_owned(),
+            options: options.iter().map(|option| option.to_string()).collect(),
+            selected: None,
+            border: Themed::new(BorderStyle::ASCII),
+            border_style: Themed::new(Style::default()),
+            style: Themed::new(Style::default()),
+            focus_style: Themed::new(Style::new().reverse()),
+            focused: false,
+            on_change: None,
+        }
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn selected_option(&self) -> Option<&str> {
+        self.selected.map(|i| self.options[i].as_str())
+    }
+
+    /// Выбирает вариант; предыдущий выбор снимается. Обработчик вызывается,
+    /// только если выбор действительно изменился; это же возвращается.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index >= self.options.len() || self.selected == Some(index) {
+            return false;
+        }
+        self.selected = Some(index);
+        if let Some(on_change) = &mut self.on_change {
+            on_change(index);
+        }
+        true
+    }
+
+    pub fn set_border(&mut self, border: BorderStyle) {
+        self.border.set(border);
+    }
+
+    pub fn set_border_style(&mut self, style: Style) {
+        self.border_style.set(style);
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_focus_style(&mut self, style: Style) {
+        self.focus_style.set(style);
+    }
+
+    pub fn set_on_change(&mut self, on_change: impl FnMut(usize) + 'static) {
+        self.on_change = Some(Box::new(on_change));
+    }
+}
+
+impl Widget for RadioGroup {
+    fn width(&self) -> usize {
+        let options = self.options.iter().map(|option| text_width(option) + 4).max().unwrap_or(0);
+        options.max(text_width(&self.title) + 2) + 4
+    }
+
+    fn height(&self) -> usize {
+        self.options.len() + 2
+    }
+
+    fn accessible(&self) -> Accessible {
+        let accessible = Accessible::new(AccessRole::RadioGroup, &self.title);
+        match self.selected_option() {
+            Some(option) => accessible.with_value(option),
+            None => accessible.with_value("ничего не выбрано"),
+        }
+    }
+
+    fn describe(&self) -> Option<Value> {
+        let options = self.options.iter().map(|option| Value::from(option.as_str())).collect::<Vec<_>>();
+        let mut properties = vec![
+            ("type", Value::from("radio_group")),
+            ("title", Value::from(self.title.as_str())),
+            ("options", Value::from(options)),
+        ];
+        if let Some(selected) = self.selected {
+            properties.push(("selected", Value::from(selected)));
+        }
+        if self.border.get() != BorderStyle::ASCII {
+            properties.push(("border", Value::from(self.border.get().name()?)));
+        }
+        Some(Value::table(properties))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        canvas.draw_box(area, &self.border.get(), self.border_style.get());
+        let style = self.style.get();
+        if !self.title.is_empty() {
+            // заголовок врезается в верхнюю линию рамки: +- Заголовок ---+
+            canvas.put_str(area.x + 2, area.y, &format!(" {} ", self.title), style);
+        }
+        let inner = area.inset(2, 1);
+        for (i, (option, y)) in self.options.iter().zip(inner.y..inner.bottom()).enumerate() {
+            let mark = if self.selected == Some(i) { "(*)" } else { "( )" };
+            let current = self.focused && self.selected.unwrap_or(0) == i;
+            canvas.put_str(inner.x, y, mark, if current { self.focus_style.get() } else { style });
+            canvas.put_str(inner.x + 4, y, option, style);
+        }
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.border.theme(theme.window_border);
+        self.border_style.theme(theme.style(Role::Border));
+        self.style.theme(theme.style(Role::Label));
+        self.focus_style.theme(theme.style(Role::ButtonFocused));
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.options.is_empty()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let last = self.options.len().saturating_sub(1);
+        let current = self.selected.unwrap_or(0);
+        match event {
+            Event::Key(key) if key.is(KeyCode::Up) || key.is(KeyCode::Left) => self.select(current.saturating_sub(1)),
+            Event::Key(key) if key.is(KeyCode::Down) || key.is(KeyCode::Right) => {
+                self.select(if self.selected.is_some() { (current + 1).min(last) } else { 0 })
+            }
+            Event::Key(key) if key.is(KeyCode::Enter) || key.is(KeyCode::Char(' ')) => self.select(current),
+            Event::Mouse(mouse) if mouse.kind == MouseKind::Press(MouseButton::Left) => {
+                // первая строка — верхняя линия рамки
+                match mouse.y.checked_sub(1) {
+                    Some(row) => self.select(row),
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::event::MouseEvent;
+    use crate::widgets::snapshot::render;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn single_selection() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let mut group = RadioGroup::new("Размер", &["S", "M", "L"]);
+        let sink = Rc::clone(&changes);
+        group.set_on_change(move |i| sink.borrow_mut().push(i));
+
+        group.select(1);
+        group.handle_event(&Event::key(KeyCode::Down));
+        assert!(!group.handle_event(&Event::key(KeyCode::Down)), "последний вариант уже выбран");
+        assert_eq!(group.selected_option(), Some("L"));
+        let click = MouseEvent { kind: MouseKind::Press(MouseButton::Left), x: 3, y: 1 };
+        group.handle_event(&Event::Mouse(click));
+        assert_eq!(group.selected(), Some(0));
+        assert_eq!(*changes.borrow(), vec![1, 2, 0]);
+
+        let out = render(&group);
+        assert_eq!(
+            out,
+            "+- Размер -+\n\
+             | (*) S    |\n\
+             | ( ) M    |\n\
+             | ( ) L    |\n\
+             +----------+\n"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::io;
+use crate::widgets::canvas::Canvas;
+
+/// Экран терминала с памятью о последнем выведенном кадре. Следующий кадр
+/// выводится разницей с ним, поэтому перерисовка не мерцает и дёшева даже по SSH.
+pub struct Screen {
+    previous: Option<Canvas>,
+}
+
+impl Screen {
+    pub fn new() -> Screen {
+        Screen { previous: None }
+    }
+
+    /// Выводит кадр. Первый кадр и кадр другого размера рисуются целиком по очищенному экрану.
+    pub fn present(&mut self, frame: Canvas, out: &mut dyn io::Write) -> io::Result<()> {
+        match &self.previous {
+            Some(previous) if previous.size() == frame.size() => {
+                write!(out, "{}", frame.diff(previous))?;
+                out.flush()?;
+            }
+            _ => {
+                write!(out, "\x1b[2J")?;
+                frame.flush(out)?;
+            }
+        }
+        self.previous = Some(frame);
+        Ok(())
+    }
+
+    /// Забывает выведенный кадр: следующий будет нарисован целиком,
+    /// например после того, как экран испортил чужой вывод.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+}
+
+impl Default for Screen {
+    fn default() -> Screen {
+        Screen::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::layout::Size;
+    use crate::widgets::style::{Color, Style};
+
+    fn present(screen: &mut Screen, canvas: &Canvas) -> String {
+        let mut out = Vec::new();
+        screen.present(canvas.clone(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn only_changed_cells_are_sent() {
+        let mut screen = Screen::new();
+        let mut canvas = Canvas::new(Size::new(6, 2));
+        canvas.put_str(0, 0, "abcdef", Style::default());
+        assert!(present(&mut screen, &canvas).starts_with("\x1b[2J\x1b[H"));
+        assert_eq!(present(&mut screen, &canvas), "");
+
+        canvas.put_str(1, 0, "XY", Style::default());
+        canvas.put_str(4, 1, "z", Style::new().fg(Color::Red));
+        assert_eq!(present(&mut screen, &canvas), "\x1b[1;2HXY\x1b[2;5H\x1b[31mz\x1b[0m");
+
+        // широкий символ занимает две клетки, и после него курсор уже на месте
+        canvas.put_str(0, 1, "漢!", Style::default());
+        assert_eq!(present(&mut screen, &canvas), "\x1b[2;1H漢!");
+        canvas.put_str(0, 1, "ab", Style::default());
+        assert_eq!(present(&mut screen, &canvas), "\x1b[2;1Hab");
+
+        screen.invalidate();
+        assert!(present(&mut screen, &canvas).starts_with("\x1b[2J"));
+    }
+}
@@ -0,0 +1,205 @@
+use std::fs;
+use std::path::PathBuf;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::layout::{text_width, Constraints, Rect, Size};
+use crate::widgets::Widget;
+
+/// Эталонные снимки лежат в `snapshots/<имя>.txt` в корне проекта.
+/// С переменной окружения `UPDATE_SNAPSHOTS=1` эталон записывается текущим выводом;
+/// при другом значении или без неё отсутствующий снимок — ошибка,
+/// чтобы незакоммиченный эталон не проходил молча.
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots").join(format!("{}.txt", name))
+}
+
+/// Отрисовка виджета в его собственном размере; escape-последовательности
+/// заменены видимым `␛`, чтобы снимки читались глазами.
+pub fn render(widget: &dyn Widget) -> String {
+    let mut out = String::new();
+    widget.draw_into(&mut out);
+    out.replace('\x1b', "␛")
+}
+
+/// То же в заданном размере.
+pub fn render_sized(widget: &dyn Widget, size: Size) -> String {
+    let mut out = String::new();
+    widget.draw_sized(size, &mut out);
+    out.replace('\x1b', "␛")
+}
+
+/// Только символы холста, без оформления, с переводом строки после каждой строки.
+pub fn plain(canvas: &Canvas) -> String {
+    canvas.rows().map(|row| row.iter().map(|cell| cell.symbol.as_str()).collect::<String>() + "\n").collect()
+}
+
+/// Символы виджета, нарисованного прямо на холсте размера `size`.
+pub fn render_plain(widget: &dyn Widget, size: Size) -> String {
+    let mut canvas = Canvas::new(size);
+    widget.render(&mut canvas, Rect::from_size(size));
+    plain(&canvas)
+}
+
+/// Сравнивает вывод с эталоном; при расхождении паникует с построчным диффом.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|value| value == "1") {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!("снимка `{}` нет: {}\n(UPDATE_SNAPSHOTS=1 запишет эталон)\n{}", name, path.display(), actual);
+    };
+    if expected != actual {
+        panic!(
+            "снимок `{}` не совпал с {}\n(UPDATE_SNAPSHOTS=1 перезапишет эталон)\n{}",
+            name,
+            path.display(),
+            diff(&expected, actual)
+        );
+    }
+}
+
+/// Построчный дифф по наибольшей общей подпоследовательности:
+/// `-` — строка эталона, `+` — строка текущего вывода.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+    // lcs[i][j] — длина общей подпоследовательности хвостов old[i..] и new[j..]
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out += &format!("  {}\n", old[i]);
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out += &format!("+ {}\n", new[j]);
+            j += 1;
+        } else {
+            out += &format!("- {}\n", old[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Контракт размера: виджет, нарисованный в своём измеренном размере, выдаёт
+/// ровно `height` строк по `width` столбцов — ни рамки, ни содержимое не выходят за край.
+pub fn assert_size_contract(widget: &dyn Widget) {
+    let size = widget.measure(Constraints::unbounded());
+    assert_eq!(size, widget.size_hint(), "measure без ограничений должен совпадать с size_hint");
+    let mut out = String::new();
+    widget.draw_into(&mut out);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), size.height, "число строк:\n{}", out);
+    for line in lines {
+        assert_eq!(text_width(line), size.width, "ширина строки {:?}:\n{}", line, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::*;
+
+    fn form() -> Window {
+        let mut window = Window::new("Настройки");
+        window.add_widget(Box::new(Label::new("Имя пользователя:")));
+        let mut name = TextField::new(20);
+        name.set_text("Ксения");
+        window.add_widget(Box::new(name));
+        window.add_widget(Box::new(Checkbox::new("Запомнить меня")));
+        let mut buttons = HBox::new();
+        buttons.set_spacing(1);
+        buttons.add_widget(Box::new(Button::new("OK")));
+        buttons.add_widget(Box::new(Button::new("Отмена")));
+        window.add_widget(Box::new(buttons));
+        window
+    }
+
+    /// Все виджеты модуля в типичных состояниях.
+    fn widgets() -> Vec<Box<dyn Widget>> {
+        let mut table = Table::new(&["Имя", "Возраст"]);
+        table.add_text_row(&["Анна", "31"]);
+        table.add_text_row(&["Борис", "4"]);
+        let mut column = VBox::new();
+        column.set_padding(1);
+        column.add_widget(Box::new(Label::new("верх")));
+        column.add_widget(Box::new(Label::new("низ\nв две строки")));
+        let mut nested = Window::new("Внешнее");
+        nested.add_widget(Box::new(form()));
+        let mut help = Label::new("Длинный абзац справки переносится по словам");
+        help.set_wrap(Wrap::Word);
+        help.set_max_width(Some(16));
+        let mut sparkline = Sparkline::new(6);
+        sparkline.set_show_axis(true);
+        sparkline.set_values(&[3.0, 1.0, 4.0, 1.0, 5.0]);
+        let mut heavy = Button::new("Жирная");
+        heavy.set_border(BorderStyle::HEAVY);
+        vec![
+            Box::new(Label::new("строка\nи ещё одна, длиннее")),
+            Box::new(help),
+            Box::new(Button::new("OK")),
+            Box::new(Button::new("漢字")),
+            Box::new(heavy),
+            Box::new(Window::new("Пустое окно")),
+            Box::new(form()),
+            Box::new(nested),
+            Box::new(column),
+            Box::new(table),
+            Box::new(TextField::new(10)),
+            Box::new(TextArea::new(12, 3)),
+            Box::new(Checkbox::new("Флажок")),
+            Box::new(Toggle::new("Звук")),
+            Box::new(RadioGroup::new("Цвет", &["красный", "зелёный"])),
+            Box::new(ListView::new(vec!["один", "два", "три"], 8, 2)),
+            Box::new(ProgressBar::new(16, 3)),
+            Box::new(Spinner::new("Загрузка")),
+            Box::new(Gauge::new(12)),
+            Box::new(sparkline),
+            Box::new(BarChart::new(4).bar("a", 1.0).bar("b", 3.0)),
+            Box::new(LinePlot::new(10, 4).series(&[1.0, 5.0, 2.0], Color::Blue)),
+        ]
+    }
+
+    #[test]
+    fn every_widget_fills_its_size() {
+        for widget in widgets() {
+            assert_size_contract(widget.as_ref());
+        }
+    }
+
+    #[test]
+    fn nested_borders_line_up() {
+        let mut outer = Window::new("Внешнее");
+        outer.add_widget(Box::new(form()));
+        assert_eq!(outer.width(), form().width() + 4);
+        assert_snapshot("nested_windows", &render(&outer));
+    }
+
+    #[test]
+    fn golden_form() {
+        assert_snapshot("form", &render(&form()));
+    }
+
+    #[test]
+    fn golden_table() {
+        let mut table = Table::new(&["Город", "Население"]);
+        table.set_column_align(1, Align::Right);
+        table.add_text_row(&["Москва", "13 010 112"]);
+        table.add_text_row(&["Казань", "1 308 660"]);
+        assert_snapshot("table", &render(&table));
+    }
+
+    #[test]
+    fn diff_marks_changed_lines() {
+        assert_eq!(diff("a\nb\nc\n", "a\nx\nc\n"), "  a\n+ x\n- b\n  c\n");
+    }
+}
@@ -0,0 +1,155 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::chart::{draw_axes, draw_column, format_value, label_width, Scale};
+use crate::widgets::layout::Rect;
+use crate::widgets::style::{Color, Style};
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Строчный график ряда значений блочными символами `▁▂▃▄▅▆▇█`, по столбцу на значение.
+/// Хранит не больше `width` последних значений, так что метрики можно просто дописывать через `push`.
+pub struct Sparkline {
+    values: Vec<f64>,
+    width: usize,
+    height: usize,
+    scale: Option<Scale>,
+    show_axis: bool,
+    style: Themed<Style>,
+    axis_style: Themed<Style>,
+    dirty: bool,
+}
+
+impl Sparkline {
+    pub fn new(width: usize) -> Sparkline {
+        Sparkline {
+            values: Vec::new(),
+            width,
+            height: 1,
+            scale: None,
+            show_axis: false,
+            style: Themed::new(Style::new().fg(Color::Green)),
+            axis_style: Themed::new(Style::default()),
+            dirty: false,
+        }
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    pub fn set_values(&mut self, values: &[f64]) {
+        self.values = values[values.len().saturating_sub(self.width)..].to_vec();
+        self.dirty = true;
+    }
+
+    /// Дописывает значение справа; самое старое уходит, если места нет.
+    pub fn push(&mut self, value: f64) {
+        self.values.push(value);
+        if self.values.len() > self.width {
+            self.values.remove(0);
+        }
+        self.dirty = true;
+    }
+
+    /// Высота в строках; каждая добавляет восемь уровней.
+    pub fn set_height(&mut self, height: usize) {
+        self.height = height.max(1);
+    }
+
+    /// Постоянная шкала вместо подобранной по данным.
+    pub fn set_scale(&mut self, scale: Option<Scale>) {
+        self.scale = scale;
+        self.dirty = true;
+    }
+
+    /// Ось с подписями шкалы слева и чертой снизу.
+    pub fn set_show_axis(&mut self, show: bool) {
+        self.show_axis = show;
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_axis_style(&mut self, style: Style) {
+        self.axis_style.set(style);
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.scale.unwrap_or_else(|| Scale::fit(self.values.iter().copied(), true))
+    }
+}
+
+impl Widget for Sparkline {
+    fn width(&self) -> usize {
+        match self.show_axis {
+            true => label_width(self.scale()) + 1 + self.width,
+            false => self.width,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.height + usize::from(self.show_axis)
+    }
+
+    fn accessible(&self) -> Accessible {
+        let value = match self.values.last() {
+            Some(last) => {
+                let min = self.values.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = self.values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                format!("последнее {}, мин {}, макс {}", format_value(*last), format_value(min), format_value(max))
+            }
+            None => "нет данных".to_owned(),
+        };
+        Accessible::new(AccessRole::Chart, "").with_value(value)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Accent));
+        self.axis_style.theme(theme.style(Role::Border));
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let scale = self.scale();
+        let plot = match self.show_axis {
+            true => draw_axes(canvas, area, scale, self.axis_style.get()),
+            false => area,
+        };
+        // не влезающие значения отбрасываются слева: свежие данные важнее
+        let visible = &self.values[self.values.len().saturating_sub(plot.width)..];
+        for (i, &value) in visible.iter().enumerate() {
+            draw_column(canvas, plot.x + i, 1, plot, 0.0, scale.ratio(value), self.style.get());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render;
+
+    #[test]
+    fn blocks_scale_to_the_largest_value() {
+        let mut sparkline = Sparkline::new(8);
+        sparkline.set_style(Style::default());
+        sparkline.set_values(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        assert_eq!(sparkline.values().len(), 8);
+        assert_eq!(render(&sparkline), "▁▂▃▄▅▆▇█\n");
+        sparkline.push(0.0);
+        assert_eq!(render(&sparkline), "▂▃▄▅▆▇█ \n");
+
+        sparkline.set_height(2);
+        sparkline.set_show_axis(true);
+        sparkline.set_scale(Some(Scale::new(0.0, 8.0)));
+        assert_eq!(render(&sparkline), "8|   ▂▄▆█ \n0|▄▆█████ \n +--------\n");
+        assert!(sparkline.accessible().value.unwrap().starts_with("последнее 0, мин 0, макс 8"));
+    }
+}
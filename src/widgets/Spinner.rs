@@ -0,0 +1,126 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::layout::{text_width, Rect};
+use crate::widgets::style::{Color, Style};
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Индикатор работы без известного объёма: кадр анимации и подпись.
+/// Кадр меняется вызовом `tick`, например при каждой перерисовке.
+pub struct Spinner {
+    label: String,
+    frames: Vec<String>,
+    frame: usize,
+    style: Themed<Style>,
+    frame_style: Themed<Style>,
+    dirty: bool,
+}
+
+impl Spinner {
+    /// Кадры по умолчанию — только ASCII, чтобы работать в любом терминале.
+    pub const LINE: [&'static str; 4] = ["|", "/", "-", "\\"];
+    pub const DOTS: [&'static str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+    pub fn new(label: &str) -> Spinner {
+        Spinner {
+            label: label.to_owned(),
+            frames: Spinner::LINE.iter().map(|frame| frame.to_string()).collect(),
+            frame: 0,
+            style: Themed::new(Style::default()),
+            frame_style: Themed::new(Style::new().fg(Color::Cyan)),
+            dirty: false,
+        }
+    }
+
+    pub fn set_label(&mut self, label: &str) {
+        self.label = label.to_owned();
+        self.dirty = true;
+    }
+
+    pub fn set_frames(&mut self, frames: &[&str]) {
+        self.frames = frames.iter().map(|frame| frame.to_string()).collect();
+        self.frame = 0;
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_frame_style(&mut self, style: Style) {
+        self.frame_style.set(style);
+    }
+
+    /// Переходит к следующему кадру.
+    pub fn tick(&mut self) {
+        if !self.frames.is_empty() {
+            self.frame = (self.frame + 1) % self.frames.len();
+        }
+        self.dirty = true;
+    }
+
+    fn frame_width(&self) -> usize {
+        self.frames.iter().map(|frame| text_width(frame)).max().unwrap_or(0)
+    }
+}
+
+impl Widget for Spinner {
+    fn width(&self) -> usize {
+        match text_width(&self.label) {
+            0 => self.frame_width(),
+            label => self.frame_width() + 1 + label,
+        }
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::Status, &self.label)
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Label));
+        self.frame_style.theme(theme.style(Role::Accent));
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        if let Some(frame) = self.frames.get(self.frame) {
+            canvas.put_str(area.x, area.y, frame, self.frame_style.get());
+        }
+        if !self.label.is_empty() {
+            canvas.put_str(area.x + self.frame_width() + 1, area.y, &self.label, self.style.get());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render;
+
+    #[test]
+    fn frames_cycle() {
+        let mut spinner = Spinner::new("Сборка");
+        spinner.set_frame_style(Style::default());
+        let mut frames = Vec::new();
+        for _ in 0..5 {
+            frames.push(render(&spinner));
+            spinner.tick();
+        }
+        assert_eq!(frames, ["| Сборка\n", "/ Сборка\n", "- Сборка\n", "\\ Сборка\n", "| Сборка\n"]);
+        assert!(spinner.is_dirty());
+        spinner.mark_clean();
+        assert!(!spinner.is_dirty());
+        spinner.set_frames(&Spinner::DOTS);
+        assert_eq!(spinner.width(), 8);
+    }
+}
@@ -0,0 +1,321 @@
+use std::cell::Cell;
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::border::BorderStyle;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::event::{Event, KeyCode, MouseButton, MouseKind};
+use crate::widgets::layout::{Constraints, Rect, Size};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Как `SplitPane` делит место.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Панели рядом, разделитель — вертикальная черта.
+    Horizontal,
+    /// Панели одна над другой, разделитель — горизонтальная черта.
+    Vertical,
+}
+
+/// Две панели и разделитель между ними. Разделитель принимает фокус и двигается
+/// стрелками вдоль оси (Home и End — до упора) или перетаскиванием мышью.
+pub struct SplitPane {
+    orientation: Orientation,
+    first: Box<dyn Widget>,
+    second: Box<dyn Widget>,
+    /// Размер первой панели вдоль оси; `None` — поровну.
+    position: Option<usize>,
+    /// Наименьший размер каждой панели, до которого разделитель можно сдвинуть.
+    min_size: usize,
+    /// Длина вдоль оси при последней отрисовке: по ней клавиши и мышь знают, где край.
+    length: Cell<usize>,
+    dragging: bool,
+    focused: bool,
+    border: Themed<BorderStyle>,
+    border_style: Themed<Style>,
+    focus_style: Style,
+    dirty: bool,
+}
+
+impl SplitPane {
+    pub fn new(orientation: Orientation, first: Box<dyn Widget>, second: Box<dyn Widget>) -> SplitPane {
+        SplitPane {
+            orientation,
+            first,
+            second,
+            position: None,
+            min_size: 1,
+            length: Cell::new(0),
+            dragging: false,
+            focused: false,
+            border: Themed::new(BorderStyle::ASCII),
+            border_style: Themed::new(Style::default()),
+            focus_style: Style::new().reverse(),
+            dirty: false,
+        }
+    }
+
+    pub fn horizontal(first: impl Widget + 'static, second: impl Widget + 'static) -> SplitPane {
+        SplitPane::new(Orientation::Horizontal, Box::new(first), Box::new(second))
+    }
+
+    pub fn vertical(first: impl Widget + 'static, second: impl Widget + 'static) -> SplitPane {
+        SplitPane::new(Orientation::Vertical, Box::new(first), Box::new(second))
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn first(&self) -> &dyn Widget {
+        self.first.as_ref()
+    }
+
+    pub fn first_mut(&mut self) -> &mut dyn Widget {
+        self.first.as_mut()
+    }
+
+    pub fn second(&self) -> &dyn Widget {
+        self.second.as_ref()
+    }
+
+    pub fn second_mut(&mut self) -> &mut dyn Widget {
+        self.second.as_mut()
+    }
+
+    /// Размер первой панели в клетках; `None` делит место поровну.
+    pub fn set_position(&mut self, position: Option<usize>) {
+        self.position = position;
+        self.dirty = true;
+    }
+
+    /// Размер первой панели при последней отрисовке.
+    pub fn position(&self) -> usize {
+        self.resolve(self.length.get())
+    }
+
+    pub fn set_min_size(&mut self, min_size: usize) {
+        self.min_size = min_size;
+    }
+
+    pub fn set_border(&mut self, border: BorderStyle) {
+        self.border.set(border);
+    }
+
+    pub fn set_border_style(&mut self, style: Style) {
+        self.border_style.set(style);
+    }
+
+    pub fn set_focus_style(&mut self, style: Style) {
+        self.focus_style = style;
+    }
+
+    /// Размер вдоль оси деления и поперёк неё.
+    fn along(&self, size: Size) -> (usize, usize) {
+        match self.orientation {
+            Orientation::Horizontal => (size.width, size.height),
+            Orientation::Vertical => (size.height, size.width),
+        }
+    }
+
+    /// Размер первой панели при длине `length`: без разделителя и не меньше `min_size` у каждой.
+    fn resolve(&self, length: usize) -> usize {
+        let panes = length.saturating_sub(1);
+        let position = self.position.unwrap_or(panes / 2);
+        let max = panes.saturating_sub(self.min_size);
+        position.min(max).max(self.min_size.min(max))
+    }
+
+    /// Области первой панели, разделителя и второй панели.
+    fn layout(&self, area: Rect) -> (Rect, Rect, Rect) {
+        let (length, _) = self.along(area.size());
+        let first = self.resolve(length);
+        let rest = length.saturating_sub(first + 1);
+        match self.orientation {
+            Orientation::Horizontal => (
+                Rect::new(area.x, area.y, first, area.height),
+                Rect::new(area.x + first, area.y, 1.min(area.width), area.height),
+                Rect::new(area.x + first + 1, area.y, rest, area.height),
+            ),
+            Orientation::Vertical => (
+                Rect::new(area.x, area.y, area.width, first),
+                Rect::new(area.x, area.y + first, area.width, 1.min(area.height)),
+                Rect::new(area.x, area.y + first + 1, area.width, rest),
+            ),
+        }
+    }
+
+    /// До первой отрисовки длина неизвестна, и сдвигать разделитель не к чему.
+    fn move_to(&mut self, position: usize) -> bool {
+        let length = self.length.get();
+        if length == 0 {
+            return false;
+        }
+        let old = self.resolve(length);
+        // запоминается уже подрезанное положение, чтобы за краем не копились лишние шаги
+        self.position = Some(position);
+        self.position = Some(self.resolve(length));
+        self.dirty = true;
+        self.position != Some(old)
+    }
+}
+
+impl Widget for SplitPane {
+    fn width(&self) -> usize {
+        self.size_hint().width
+    }
+
+    fn height(&self) -> usize {
+        self.size_hint().height
+    }
+
+    fn size_hint(&self) -> Size {
+        let (first, first_cross) = self.along(self.first.size_hint());
+        let (second, second_cross) = self.along(self.second.size_hint());
+        let length = self.position.unwrap_or(first).max(first) + 1 + second;
+        let cross = first_cross.max(second_cross);
+        match self.orientation {
+            Orientation::Horizontal => Size::new(length, cross),
+            Orientation::Vertical => Size::new(cross, length),
+        }
+    }
+
+    fn accessible(&self) -> Accessible {
+        let length = self.length.get().saturating_sub(1).max(1);
+        let percent = self.position() * 100 / length;
+        Accessible::new(AccessRole::Splitter, "").with_value(format!("{}%", percent))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        self.length.set(self.along(area.size()).0);
+        let (first, divider, second) = self.layout(area);
+        let border = self.border.get();
+        let style = if self.focused { self.focus_style } else { self.border_style.get() };
+        let line = match self.orientation {
+            Orientation::Horizontal => border.vertical,
+            Orientation::Vertical => border.horizontal,
+        };
+        for y in divider.y..divider.bottom() {
+            for x in divider.x..divider.right() {
+                canvas.put_border(x, y, &line.to_string(), style);
+            }
+        }
+        canvas.render_widget(self.first.as_ref(), first);
+        canvas.render_widget(self.second.as_ref(), second);
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let (back, forward) = match self.orientation {
+            Orientation::Horizontal => (KeyCode::Left, KeyCode::Right),
+            Orientation::Vertical => (KeyCode::Up, KeyCode::Down),
+        };
+        let position = self.position();
+        match event {
+            Event::Key(key) if key.is(back) => self.move_to(position.saturating_sub(1)),
+            Event::Key(key) if key.is(forward) => self.move_to(position + 1),
+            Event::Key(key) if key.is(KeyCode::Home) => self.move_to(0),
+            Event::Key(key) if key.is(KeyCode::End) => self.move_to(usize::MAX),
+            Event::Mouse(mouse) => {
+                let (along, _) = self.along(Size::new(mouse.x, mouse.y));
+                match mouse.kind {
+                    MouseKind::Press(MouseButton::Left) if along == position => {
+                        self.dragging = true;
+                        true
+                    }
+                    MouseKind::Drag(MouseButton::Left) if self.dragging => self.move_to(along),
+                    MouseKind::Release if self.dragging => {
+                        self.dragging = false;
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty || self.first.is_dirty() || self.second.is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+        self.first.mark_clean();
+        self.second.mark_clean();
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.border.theme(theme.window_border);
+        self.border_style.theme(theme.style(Role::Border));
+        self.first.apply_theme(theme);
+        self.second.apply_theme(theme);
+    }
+
+    fn children(&self) -> Vec<&dyn Widget> {
+        vec![self.first.as_ref(), self.second.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        vec![self.first.as_mut(), self.second.as_mut()]
+    }
+
+    /// Панели занимают свои части целиком: содержимое растягивается до разделителя.
+    fn child_areas(&self, area: Rect) -> Vec<Rect> {
+        let (first, _, second) = self.layout(area);
+        vec![first, second]
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        constraints.constrain(self.size_hint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::app::App;
+    use crate::widgets::event::MouseEvent;
+    use crate::widgets::{Button, Label};
+    use crate::widgets::snapshot::render_plain;
+
+    #[test]
+    fn divides_in_half_and_moves_with_keys() {
+        let mut split = SplitPane::horizontal(Label::new("лево"), Label::new("право"));
+        assert_eq!(split.size_hint(), Size::new(10, 1));
+        assert!(!split.handle_event(&Event::key(KeyCode::Right)), "до отрисовки клавиши не действуют");
+        assert_eq!(render_plain(&split, Size::new(11, 2)), "лево |право\n     |     \n");
+        split.set_focused(true);
+        assert!(split.handle_event(&Event::key(KeyCode::Left)));
+        assert_eq!(render_plain(&split, Size::new(11, 2)), "лево|право \n    |      \n");
+        assert_eq!(split.position(), 4);
+        assert!(split.handle_event(&Event::key(KeyCode::End)));
+        assert_eq!(split.position(), 9);
+        assert!(!split.handle_event(&Event::key(KeyCode::Right)), "упёрся в край");
+        split.set_min_size(3);
+        split.handle_event(&Event::key(KeyCode::Home));
+        assert_eq!(split.position(), 3);
+    }
+
+    #[test]
+    fn drag_divider_through_app() {
+        let mut app = App::new(Box::new(SplitPane::vertical(Button::new("верх"), Label::new("низ"))));
+        assert_eq!(app.area(), Rect::new(0, 0, 14, 5));
+        app.frame();
+        let mouse = |kind, y| Event::Mouse(MouseEvent { kind, x: 2, y });
+        app.handle_event(&mouse(MouseKind::Press(MouseButton::Left), 2));
+        assert_eq!(app.focused(), Some(&[][..]));
+        // кнопка под курсором перетаскивание не перехватывает: оно досталось разделителю
+        app.handle_event(&mouse(MouseKind::Drag(MouseButton::Left), 1));
+        app.handle_event(&mouse(MouseKind::Release, 1));
+        assert_eq!(app.focused(), Some(&[][..]));
+        assert_eq!(app.root().child_areas(app.area()), vec![Rect::new(0, 0, 14, 1), Rect::new(0, 2, 14, 3)]);
+    }
+}
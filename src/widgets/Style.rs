@@ -0,0 +1,232 @@
+use std::fmt::Write;
+
+/// Цвет текста или фона: 16 стандартных цветов, палитра из 256 цветов или truecolor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+/// Палитра xterm для 16 стандартных цветов.
+const BASIC_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+impl Color {
+    /// Цвет в RGB — для вывода вне терминала (HTML, SVG).
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        let basic = |n: usize| BASIC_RGB[n];
+        match self {
+            Color::Black => basic(0),
+            Color::Red => basic(1),
+            Color::Green => basic(2),
+            Color::Yellow => basic(3),
+            Color::Blue => basic(4),
+            Color::Magenta => basic(5),
+            Color::Cyan => basic(6),
+            Color::White => basic(7),
+            Color::BrightBlack => basic(8),
+            Color::BrightRed => basic(9),
+            Color::BrightGreen => basic(10),
+            Color::BrightYellow => basic(11),
+            Color::BrightBlue => basic(12),
+            Color::BrightMagenta => basic(13),
+            Color::BrightCyan => basic(14),
+            Color::BrightWhite => basic(15),
+            Color::Indexed(n @ 0..=15) => basic(n as usize),
+            Color::Indexed(n @ 16..=231) => {
+                // куб 6×6×6
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                let n = n - 16;
+                (level(n / 36), level(n / 6 % 6), level(n % 6))
+            }
+            Color::Indexed(n) => {
+                let gray = 8 + (n - 232) * 10;
+                (gray, gray, gray)
+            }
+            Color::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+
+    /// Запись `#rrggbb` для CSS и SVG.
+    pub fn to_hex(self) -> String {
+        let (r, g, b) = self.to_rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+
+    /// Параметры SGR; `base` равен 30 для текста и 40 для фона.
+    fn sgr(self, base: u8) -> String {
+        let basic = |n: u8| (base + n).to_string();
+        let bright = |n: u8| (base + 60 + n).to_string();
+        match self {
+            Color::Black => basic(0),
+            Color::Red => basic(1),
+            Color::Green => basic(2),
+            Color::Yellow => basic(3),
+            Color::Blue => basic(4),
+            Color::Magenta => basic(5),
+            Color::Cyan => basic(6),
+            Color::White => basic(7),
+            Color::BrightBlack => bright(0),
+            Color::BrightRed => bright(1),
+            Color::BrightGreen => bright(2),
+            Color::BrightYellow => bright(3),
+            Color::BrightBlue => bright(4),
+            Color::BrightMagenta => bright(5),
+            Color::BrightCyan => bright(6),
+            Color::BrightWhite => bright(7),
+            Color::Indexed(n) => format!("{};5;{}", base + 8, n),
+            Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+}
+
+/// Оформление текста: цвета и атрибуты. Выводится escape-последовательностями ANSI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub reverse: bool,
+    pub strikethrough: bool,
+}
+
+pub const RESET: &str = "\x1b[0m";
+
+impl Style {
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    pub fn fg(mut self, color: Color) -> Style {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Style {
+        self.bg = Some(color);
+        self
+    }
+
+    pub fn bold(mut self) -> Style {
+        self.bold = true;
+        self
+    }
+
+    pub fn dim(mut self) -> Style {
+        self.dim = true;
+        self
+    }
+
+    pub fn italic(mut self) -> Style {
+        self.italic = true;
+        self
+    }
+
+    pub fn underline(mut self) -> Style {
+        self.underline = true;
+        self
+    }
+
+    pub fn reverse(mut self) -> Style {
+        self.reverse = true;
+        self
+    }
+
+    pub fn strikethrough(mut self) -> Style {
+        self.strikethrough = true;
+        self
+    }
+
+    pub fn is_plain(&self) -> bool {
+        *self == Style::default()
+    }
+
+    /// Escape-последовательность, включающая этот стиль (пустая для простого текста).
+    pub fn prefix(&self) -> String {
+        let mut codes = Vec::new();
+        let attributes = [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+            (self.reverse, "7"),
+            (self.strikethrough, "9"),
+        ];
+        codes.extend(attributes.iter().filter(|(on, _)| *on).map(|(_, code)| code.to_string()));
+        codes.extend(self.fg.map(|color| color.sgr(30)));
+        codes.extend(self.bg.map(|color| color.sgr(40)));
+        if codes.is_empty() {
+            return String::new();
+        }
+        let mut prefix = String::new();
+        write!(prefix, "\x1b[{}m", codes.join(";")).unwrap();
+        prefix
+    }
+
+    /// Оборачивает текст в escape-последовательности стиля и сброса.
+    pub fn paint(&self, text: &str) -> String {
+        if self.is_plain() {
+            return text.to_owned();
+        }
+        format!("{}{}{}", self.prefix(), text, RESET)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::layout::{fit, text_width, Align};
+
+    #[test]
+    fn escape_codes() {
+        assert_eq!(Style::new().fg(Color::Red).prefix(), "\x1b[31m");
+        assert_eq!(Style::new().bold().bg(Color::BrightBlue).prefix(), "\x1b[1;104m");
+        assert_eq!(Style::new().fg(Color::Indexed(208)).prefix(), "\x1b[38;5;208m");
+        assert_eq!(Style::new().underline().bg(Color::Rgb(1, 2, 3)).prefix(), "\x1b[4;48;2;1;2;3m");
+        assert_eq!(Style::new().paint("x"), "x");
+    }
+
+    #[test]
+    fn width_ignores_escapes() {
+        let red = Style::new().fg(Color::Red).bold().paint("ошибка");
+        assert_eq!(text_width(&red), 6);
+        assert_eq!(fit(&red, 8, Align::Left), format!("{}  ", red));
+        // обрезанный текст всё равно заканчивается сбросом стиля
+        assert_eq!(fit(&red, 3, Align::Left), "\x1b[1;31mоши\x1b[0m");
+    }
+}
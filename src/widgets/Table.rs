@@ -0,0 +1,454 @@
+use std::cmp::Reverse;
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::border::BorderStyle;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::{align_name, Value};
+use crate::widgets::layout::{Align, Constraints, Rect, Size};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::{Label, Widget};
+
+/// Способ задать ширину столбца.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// По самому широкому содержимому столбца.
+    Auto,
+    /// Ровно столько столбцов терминала.
+    Fixed(usize),
+    /// Процент от ширины, доступной таблице.
+    Percent(usize),
+}
+
+struct Column {
+    header: Label,
+    width: ColumnWidth,
+    align: Align,
+}
+
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<Box<dyn Widget>>>,
+    header_rule: bool,
+    row_separators: bool,
+    border: Themed<BorderStyle>,
+    border_style: Themed<Style>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Table {
+        let columns = headers
+            .iter()
+            .map(|header| Column { header: Label::new(header), width: ColumnWidth::Auto, align: Align::Left })
+            .collect();
+        Table {
+            columns,
+            rows: Vec::new(),
+            header_rule: true,
+            row_separators: false,
+            border: Themed::new(BorderStyle::ASCII),
+            border_style: Themed::new(Style::default()),
+        }
+    }
+
+    /// Добавляет строку из виджетов; недостающие ячейки остаются пустыми.
+    pub fn add_row(&mut self, cells: Vec<Box<dyn Widget>>) {
+        self.rows.push(cells);
+    }
+
+    pub fn add_text_row(&mut self, cells: &[&str]) {
+        self.add_row(cells.iter().map(|cell| Box::new(Label::new(cell)) as Box<dyn Widget>).collect());
+    }
+
+    /// Несуществующий столбец молча пропускается.
+    pub fn set_column_width(&mut self, column: usize, width: ColumnWidth) {
+        if let Some(col) = self.columns.get_mut(column) {
+            col.width = width;
+        }
+    }
+
+    pub fn set_column_align(&mut self, column: usize, align: Align) {
+        if let Some(col) = self.columns.get_mut(column) {
+            col.align = align;
+        }
+    }
+
+    pub fn set_border(&mut self, border: BorderStyle) {
+        self.border.set(border);
+    }
+
+    pub fn set_border_style(&mut self, style: Style) {
+        self.border_style.set(style);
+    }
+
+    /// Линия `+===+` под заголовком.
+    pub fn set_header_rule(&mut self, enabled: bool) {
+        self.header_rule = enabled;
+    }
+
+    /// Линии `+---+` между строками данных.
+    pub fn set_row_separators(&mut self, enabled: bool) {
+        self.row_separators = enabled;
+    }
+
+    /// Рамки и отступы: по одной черте на границу и по пробелу с каждой стороны ячейки.
+    fn chrome_width(&self) -> usize {
+        3 * self.columns.len() + 1
+    }
+
+    fn content_width(&self, column: usize) -> usize {
+        let cells = self.rows.iter().filter_map(|row| row.get(column)).map(|cell| cell.width());
+        cells.fold(self.columns[column].header.width(), usize::max)
+    }
+
+    /// Ширины столбцов при ширине таблицы `total` (`None` — без ограничения).
+    fn column_widths(&self, total: Option<usize>) -> Vec<usize> {
+        let available = total.map(|total| total.saturating_sub(self.chrome_width()));
+        let mut widths: Vec<usize> = (0..self.columns.len())
+            .map(|i| match self.columns[i].width {
+                ColumnWidth::Auto => self.content_width(i),
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Percent(percent) => match available {
+                    Some(available) => available * percent / 100,
+                    None => self.content_width(i),
+                },
+            })
+            .collect();
+
+        if let Some(available) = available {
+            // Лишнее место отдаём последнему нефиксированному столбцу. Нехватку забираем у самых широких:
+            // сначала у `Auto`, потом у `Percent`, а фиксированные сужаем, только когда больше не у кого.
+            let priority = |column: usize| match self.columns[column].width {
+                ColumnWidth::Auto => 0,
+                ColumnWidth::Percent(_) => 1,
+                ColumnWidth::Fixed(_) => 2,
+            };
+            let sum: usize = widths.iter().sum();
+            if sum < available {
+                if let Some(last) = (0..widths.len()).rev().find(|&i| priority(i) < 2) {
+                    widths[last] += available - sum;
+                }
+            }
+            for _ in available..sum {
+                let candidates = (0..widths.len()).filter(|&i| widths[i] > 0);
+                match candidates.min_by_key(|&i| (priority(i), Reverse(widths[i]))) {
+                    Some(i) => widths[i] -= 1,
+                    None => break,
+                }
+            }
+        }
+        widths
+    }
+
+    /// Горизонтальная линия через всю таблицу: `left`, затем столбцы, разделённые `cross`.
+    fn rule(widths: &[usize], left: char, fill: char, cross: char, right: char) -> String {
+        let mut line = String::new();
+        line.push(left);
+        for (i, &width) in widths.iter().enumerate() {
+            if i > 0 {
+                line.push(cross);
+            }
+            line.extend(std::iter::repeat_n(fill, width + 2));
+        }
+        line.push(right);
+        line
+    }
+
+    /// Ячейки строки; недостающие ячейки данных пустые.
+    fn cells(&self, line: &Line) -> Vec<Option<&dyn Widget>> {
+        match *line {
+            Line::Header => self.columns.iter().map(|column| Some(&column.header as &dyn Widget)).collect(),
+            Line::Row(row) => (0..self.columns.len())
+                .map(|column| self.rows[row].get(column).map(|cell| cell.as_ref()))
+                .collect(),
+            Line::Rule(_) => Vec::new(),
+        }
+    }
+
+    /// Место ячейки внутри `slot` (столбец без отступов): по размеру содержимого и выравниванию столбца.
+    fn cell_area(&self, cell: &dyn Widget, column: usize, slot: Rect) -> Rect {
+        let size = cell.measure(Constraints::loose(slot.size()));
+        let offset = match self.columns[column].align {
+            Align::Left | Align::Justify => 0,
+            Align::Center => (slot.width - size.width) / 2,
+            Align::Right => slot.width - size.width,
+        };
+        Rect::new(slot.x + offset, slot.y, size.width, size.height)
+    }
+
+    /// Высота строки — по самой высокой ячейке; ячейки могут быть многострочными.
+    fn line_height(&self, line: &Line, widths: &[usize]) -> usize {
+        if let Line::Rule(_) = line {
+            return 1;
+        }
+        let heights = self.cells(line).into_iter().zip(widths).map(|(cell, &width)| match cell {
+            Some(cell) => cell.measure(Constraints::loose(Size::new(width, usize::MAX))).height,
+            None => 0,
+        });
+        heights.max().unwrap_or(0).max(1)
+    }
+
+    /// Порядок линий и строк таблицы сверху вниз.
+    fn lines(&self, widths: &[usize]) -> Vec<Line> {
+        let b = &self.border.get();
+        let bottom = Table::rule(widths, b.bottom_left, b.horizontal, b.bottom_tee, b.bottom_right);
+        let separator = Table::rule(widths, b.left_tee, b.horizontal, b.cross, b.right_tee);
+
+        let mut lines = vec![Line::Rule(Table::rule(widths, b.top_left, b.horizontal, b.top_tee, b.top_right))];
+        lines.push(Line::Header);
+        if self.rows.is_empty() {
+            lines.push(Line::Rule(bottom));
+            return lines;
+        }
+        if self.header_rule {
+            lines.push(Line::Rule(Table::rule(widths, b.header_left, b.header, b.header_cross, b.header_right)));
+        } else {
+            lines.push(Line::Rule(separator.clone()));
+        }
+        for row in 0..self.rows.len() {
+            if row > 0 && self.row_separators {
+                lines.push(Line::Rule(separator.clone()));
+            }
+            lines.push(Line::Row(row));
+        }
+        lines.push(Line::Rule(bottom));
+        lines
+    }
+}
+
+enum Line {
+    Rule(String),
+    Header,
+    Row(usize),
+}
+
+impl Widget for Table {
+    fn width(&self) -> usize {
+        self.column_widths(None).iter().sum::<usize>() + self.chrome_width()
+    }
+
+    fn height(&self) -> usize {
+        let widths = self.column_widths(None);
+        self.lines(&widths).iter().map(|line| self.line_height(line, &widths)).sum()
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.border.theme(theme.window_border);
+        self.border_style.theme(theme.style(Role::Border));
+        for column in &mut self.columns {
+            column.header.apply_theme(theme);
+        }
+        for cell in self.rows.iter_mut().flatten() {
+            cell.apply_theme(theme);
+        }
+    }
+
+    fn accessible(&self) -> Accessible {
+        let headers: Vec<&str> = self.columns.iter().map(|column| column.header.text()).collect();
+        Accessible::new(AccessRole::Table, &headers.join(", ")).with_value(format!("строк: {}", self.rows.len()))
+    }
+
+    fn describe(&self) -> Option<Value> {
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| {
+                let mut properties = vec![("title", Value::from(column.header.text()))];
+                match column.width {
+                    ColumnWidth::Auto => {}
+                    ColumnWidth::Fixed(width) => properties.push(("width", Value::from(width))),
+                    ColumnWidth::Percent(percent) => properties.push(("percent", Value::from(percent))),
+                }
+                if column.align != Align::Left {
+                    properties.push(("align", Value::from(align_name(column.align))));
+                }
+                Value::table(properties)
+            })
+            .collect::<Vec<_>>();
+        let mut rows = Vec::new();
+        for row in &self.rows {
+            let cells = row.iter().map(|cell| cell.describe()).collect::<Option<Vec<_>>>()?;
+            rows.push(Value::from(cells));
+        }
+        let mut properties = vec![("type", Value::from("table")), ("columns", Value::from(columns))];
+        if !rows.is_empty() {
+            properties.push(("rows", Value::from(rows)));
+        }
+        if !self.header_rule {
+            properties.push(("header_rule", Value::from(false)));
+        }
+        if self.row_separators {
+            properties.push(("row_separators", Value::from(true)));
+        }
+        if self.border.get() != BorderStyle::ASCII {
+            properties.push(("border", Value::from(self.border.get().name()?)));
+        }
+        Some(Value::table(properties))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let widths = self.column_widths(Some(area.width));
+        let vertical = self.border.get().vertical.to_string();
+        let border_style = self.border_style.get();
+        let mut y = area.y;
+
+        for line in self.lines(&widths) {
+            let height = self.line_height(&line, &widths);
+            if let Line::Rule(rule) = &line {
+                canvas.put_border(area.x, y, rule, border_style);
+                y += 1;
+                continue;
+            }
+
+            let mut x = area.x;
+            for (i, cell) in self.cells(&line).into_iter().enumerate() {
+                for row in y..y + height {
+                    canvas.put_border(x, row, &vertical, border_style);
+                }
+                if let Some(cell) = cell {
+                    canvas.render_widget(cell, self.cell_area(cell, i, Rect::new(x + 2, y, widths[i], height)));
+                }
+                x += widths[i] + 3;
+            }
+            for row in y..y + height {
+                canvas.put_border(x, row, &vertical, border_style);
+            }
+            y += height;
+        }
+    }
+
+    /// Ячейки данных по строкам, слева направо; заголовки — часть имени самой таблицы.
+    fn children(&self) -> Vec<&dyn Widget> {
+        let columns = self.columns.len();
+        self.rows.iter().flat_map(|row| row.iter().take(columns)).map(|cell| cell.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        let columns = self.columns.len();
+        self.rows
+            .iter_mut()
+            .flat_map(|row| row.iter_mut().take(columns))
+            .map(|cell| cell.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+
+    fn child_areas(&self, area: Rect) -> Vec<Rect> {
+        let widths = self.column_widths(Some(area.width));
+        let mut areas = Vec::new();
+        let mut y = area.y;
+        for line in self.lines(&widths) {
+            let height = self.line_height(&line, &widths);
+            if let Line::Row(_) = line {
+                let mut x = area.x;
+                for (i, cell) in self.cells(&line).into_iter().enumerate() {
+                    if let Some(cell) = cell {
+                        areas.push(self.cell_area(cell, i, Rect::new(x + 2, y, widths[i], height)));
+                    }
+                    x += widths[i] + 3;
+                }
+            }
+            y += height;
+        }
+        areas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::access::{find_by_id, transcript};
+    use crate::widgets::Button;
+    use crate::widgets::snapshot::render_sized;
+
+    #[test]
+    fn auto_widths() {
+        let mut table = Table::new(&["Сервис", "Статус"]);
+        table.add_text_row(&["api", "ok"]);
+        table.add_text_row(&["db", "down"]);
+        table.set_column_align(1, Align::Right);
+        assert_eq!(table.height(), 6);
+        assert_eq!(
+            render_sized(&table, table.size_hint()),
+            "+--------+--------+\n\
+             | Сервис | Статус |\n\
+             +========+========+\n\
+             | api    |     ok |\n\
+             | db     |   down |\n\
+             +--------+--------+\n"
+        );
+    }
+
+    #[test]
+    fn fixed_and_percent_widths() {
+        let mut table = Table::new(&["a", "b"]);
+        table.set_column_width(0, ColumnWidth::Fixed(3));
+        table.set_column_width(1, ColumnWidth::Percent(50));
+        table.set_column_width(2, ColumnWidth::Fixed(9));
+        table.set_row_separators(true);
+        table.set_header_rule(false);
+        table.add_text_row(&["1", "2"]);
+        table.add_text_row(&["3", "4"]);
+        let out = render_sized(&table, Size::new(17, 7));
+        assert_eq!(
+            out,
+            "+-----+---------+\n\
+             | a   | b       |\n\
+             +-----+---------+\n\
+             | 1   | 2       |\n\
+             +-----+---------+\n\
+             | 3   | 4       |\n\
+             +-----+---------+\n"
+        );
+    }
+
+    #[test]
+    fn light_border_junctions() {
+        let mut table = Table::new(&["a", "b"]);
+        table.set_border(BorderStyle::LIGHT);
+        table.set_row_separators(true);
+        table.add_text_row(&["1", "2"]);
+        table.add_text_row(&["3", "4"]);
+        assert_eq!(
+            render_sized(&table, table.size_hint()),
+            "┌───┬───┐\n\
+             │ a │ b │\n\
+             ╞═══╪═══╡\n\
+             │ 1 │ 2 │\n\
+             ├───┼───┤\n\
+             │ 3 │ 4 │\n\
+             └───┴───┘\n"
+        );
+    }
+
+    #[test]
+    fn cells_are_children() {
+        let mut table = Table::new(&["Имя", "Действие"]);
+        table.set_column_align(1, Align::Right);
+        table.add_row(vec![Box::new(Label::new("Анна")), Box::new(Button::new("Удалить").with_id("delete"))]);
+        table.add_text_row(&["Борис"]);
+        let area = Rect::from_size(table.size_hint());
+        assert_eq!(table.children().len(), 3);
+        assert_eq!(
+            table.child_areas(area),
+            vec![Rect::new(2, 3, 4, 1), Rect::new(10, 3, 17, 3), Rect::new(2, 6, 5, 1)]
+        );
+        assert_eq!(find_by_id(&table, "delete"), Some(vec![1]));
+        assert!(transcript(&table, None).contains("Удалить"));
+    }
+
+    #[test]
+    fn fixed_columns_keep_their_width() {
+        let mut table = Table::new(&["имя", "код"]);
+        table.set_column_width(1, ColumnWidth::Fixed(3));
+        table.add_text_row(&["длинное имя", "42"]);
+        // лишнее место досталось первому столбцу, последний фиксированный не растёт
+        assert_eq!(table.column_widths(Some(24)), vec![14, 3]);
+        let mut wide = Table::new(&["a", "b", "c"]);
+        wide.set_column_width(0, ColumnWidth::Fixed(4));
+        wide.set_column_width(1, ColumnWidth::Percent(50));
+        wide.add_text_row(&["", "", "длинный текст"]);
+        // нехватку сначала отдаёт Auto, потом Percent, фиксированный — последним
+        assert_eq!(wide.column_widths(Some(10 + 13)), vec![4, 6, 3]);
+        assert_eq!(wide.column_widths(Some(10 + 5)), vec![4, 1, 0]);
+        assert_eq!(wide.column_widths(Some(10 + 2)), vec![2, 0, 0]);
+    }
+}
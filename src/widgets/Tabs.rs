@@ -0,0 +1,298 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::border::BorderStyle;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::event::{Event, KeyCode, MouseButton, MouseKind};
+use crate::widgets::layout::{text_width, Constraints, Rect, Size};
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+struct Tab {
+    title: String,
+    widget: Box<dyn Widget>,
+}
+
+/// Вкладки: строка заголовков, под ней черта и содержимое выбранной вкладки.
+/// Остальные вкладки не рисуются и не видны в дереве, поэтому фокус в них не попадает.
+/// Высота — по самой высокой вкладке, чтобы при переключении ничего не прыгало.
+pub struct Tabs {
+    tabs: Vec<Tab>,
+    selected: usize,
+    focused: bool,
+    style: Style,
+    selected_style: Style,
+    border: Themed<BorderStyle>,
+    border_style: Themed<Style>,
+    on_change: Option<Box<dyn FnMut(usize)>>,
+    dirty: bool,
+}
+
+impl Tabs {
+    pub fn new() -> Tabs {
+        Tabs {
+            tabs: Vec::new(),
+            selected: 0,
+            focused: false,
+            style: Style::default(),
+            selected_style: Style::new().reverse(),
+            border: Themed::new(BorderStyle::ASCII),
+            border_style: Themed::new(Style::default()),
+            on_change: None,
+            dirty: false,
+        }
+    }
+
+    pub fn add_tab(&mut self, title: &str, widget: Box<dyn Widget>) {
+        self.tabs.push(Tab { title: title.to_owned(), widget });
+    }
+
+    pub fn tab(mut self, title: &str, widget: impl Widget + 'static) -> Tabs {
+        self.add_tab(title, Box::new(widget));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_title(&self) -> Option<&str> {
+        self.tabs.get(self.selected).map(|tab| tab.title.as_str())
+    }
+
+    /// Переключает на вкладку `index`. Если фокус был внутри прежней вкладки,
+    /// его надо перевести заново (`App::set_focus`).
+    pub fn set_selected(&mut self, index: usize) {
+        if index >= self.tabs.len() || index == self.selected {
+            return;
+        }
+        self.selected = index;
+        self.dirty = true;
+        if let Some(on_change) = &mut self.on_change {
+            on_change(index);
+        }
+    }
+
+    /// Содержимое вкладки, в том числе скрытой.
+    pub fn page(&self, index: usize) -> Option<&dyn Widget> {
+        self.tabs.get(index).map(|tab| tab.widget.as_ref())
+    }
+
+    pub fn page_mut(&mut self, index: usize) -> Option<&mut dyn Widget> {
+        self.tabs.get_mut(index).map(|tab| tab.widget.as_mut() as &mut dyn Widget)
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style = style;
+    }
+
+    /// Рамка даёт символы черты под заголовками и между ними.
+    pub fn set_border(&mut self, border: BorderStyle) {
+        self.border.set(border);
+    }
+
+    pub fn set_border_style(&mut self, style: Style) {
+        self.border_style.set(style);
+    }
+
+    pub fn set_on_change(&mut self, on_change: impl FnMut(usize) + 'static) {
+        self.on_change = Some(Box::new(on_change));
+    }
+
+    /// Левые края заголовков и их ширина; между заголовками — столбец под черту.
+    fn titles(&self) -> Vec<(usize, usize)> {
+        let mut x = 0;
+        self.tabs
+            .iter()
+            .map(|tab| {
+                let width = text_width(&tab.title) + 2;
+                x += width + 1;
+                (x - width - 1, width)
+            })
+            .collect()
+    }
+
+    fn header_width(&self) -> usize {
+        self.titles().last().map(|(x, width)| x + width).unwrap_or(0)
+    }
+
+    fn page_area(area: Rect) -> Rect {
+        Rect::new(area.x, area.y + 2, area.width, area.height.saturating_sub(2))
+    }
+}
+
+impl Default for Tabs {
+    fn default() -> Tabs {
+        Tabs::new()
+    }
+}
+
+impl Widget for Tabs {
+    fn width(&self) -> usize {
+        let pages = self.tabs.iter().map(|tab| tab.widget.width()).max().unwrap_or(0);
+        self.header_width().max(pages)
+    }
+
+    fn height(&self) -> usize {
+        2 + self.tabs.iter().map(|tab| tab.widget.height()).max().unwrap_or(0)
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        let pages = constraints.shrink(0, 2);
+        let mut size = Size::new(self.header_width(), 0);
+        for tab in &self.tabs {
+            let page = tab.widget.measure(pages);
+            size.width = size.width.max(page.width);
+            size.height = size.height.max(page.height);
+        }
+        constraints.constrain(Size::new(size.width, size.height + 2))
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::TabList, "").with_value(self.selected_title().unwrap_or_default())
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        if area.height < 2 {
+            return;
+        }
+        let border = self.border.get();
+        let border_style = self.border_style.get();
+        canvas.fill(Rect::new(area.x, area.y, area.width, 1), " ", self.style);
+        for (i, (tab, (x, width))) in self.tabs.iter().zip(self.titles()).enumerate() {
+            let x = area.x + x;
+            if i > 0 {
+                canvas.put_border(x - 1, area.y, &border.vertical.to_string(), border_style);
+            }
+            let style = match (i == self.selected, self.focused) {
+                (true, true) => self.selected_style.bold(),
+                (true, false) => self.selected_style,
+                (false, _) => self.style,
+            };
+            canvas.put_str(x, area.y, &format!(" {} ", tab.title), style);
+            canvas.set_style(Rect::new(x, area.y, width, 1), style);
+        }
+        canvas.put_border(area.x, area.y + 1, &border.horizontal.to_string().repeat(area.width), border_style);
+        if let Some(tab) = self.tabs.get(self.selected) {
+            canvas.render_widget(tab.widget.as_ref(), Tabs::page_area(area));
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.tabs.is_empty()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Стрелки, Home и End переключают вкладки, щелчок по заголовку выбирает вкладку.
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let count = self.tabs.len();
+        if count == 0 {
+            return false;
+        }
+        let index = match event {
+            Event::Key(key) if key.is(KeyCode::Left) => (self.selected + count - 1) % count,
+            Event::Key(key) if key.is(KeyCode::Right) => (self.selected + 1) % count,
+            Event::Key(key) if key.is(KeyCode::Home) => 0,
+            Event::Key(key) if key.is(KeyCode::End) => count - 1,
+            Event::Mouse(mouse) if mouse.kind == MouseKind::Press(MouseButton::Left) && mouse.y == 0 => {
+                match self.titles().iter().position(|&(x, width)| (x..x + width).contains(&mouse.x)) {
+                    Some(index) => index,
+                    None => return false,
+                }
+            }
+            _ => return false,
+        };
+        self.set_selected(index);
+        true
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty || self.tabs.get(self.selected).is_some_and(|tab| tab.widget.is_dirty())
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+        for tab in &mut self.tabs {
+            tab.widget.mark_clean();
+        }
+    }
+
+    /// Тема достаётся и скрытым вкладкам, которых нет в `children`.
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.border.theme(theme.window_border);
+        self.border_style.theme(theme.style(Role::Border));
+        for tab in &mut self.tabs {
+            tab.widget.apply_theme(theme);
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.tabs.get(self.selected).map(|tab| tab.widget.as_ref()).into_iter().collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.tabs.get_mut(self.selected).map(|tab| tab.widget.as_mut() as &mut dyn Widget).into_iter().collect()
+    }
+
+    fn child_areas(&self, area: Rect) -> Vec<Rect> {
+        let page = self.tabs.get(self.selected).map(|tab| tab.widget.measure(Constraints::loose(Tabs::page_area(area).size())));
+        page.map(|size| Rect::new(area.x, area.y + 2, size.width, size.height)).into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::app::App;
+    use crate::widgets::{Button, Label, VBox};
+    use crate::widgets::snapshot::plain;
+
+    fn tabs() -> Tabs {
+        let mut settings = VBox::new();
+        settings.add_widget(Box::new(Button::new("Применить")));
+        Tabs::new().tab("Обзор", Label::new("всё в порядке")).tab("Журнал", Label::new("пусто")).tab("Настройки", settings)
+    }
+
+    #[test]
+    fn strip_and_selected_page() {
+        let mut tabs = tabs();
+        assert_eq!(tabs.size_hint(), Size::new(28, 5));
+        let mut canvas = Canvas::new(Size::new(28, 3));
+        tabs.render(&mut canvas, Rect::new(0, 0, 28, 3));
+        assert_eq!(plain(&canvas), " Обзор | Журнал | Настройки \n----------------------------\nвсё в порядке               \n");
+        assert!(canvas.get(1, 0).unwrap().style.reverse);
+
+        assert!(tabs.handle_event(&Event::key(KeyCode::Left)));
+        assert_eq!(tabs.selected_title(), Some("Настройки"));
+        assert!(tabs.handle_event(&Event::click(9, 0)));
+        assert_eq!(tabs.selected(), 1);
+        assert!(!tabs.handle_event(&Event::click(7, 0)), "щелчок по черте между вкладками");
+        assert!(tabs.is_dirty());
+    }
+
+    #[test]
+    fn hidden_pages_are_out_of_focus_chain() {
+        let mut app = App::new(Box::new(tabs()));
+        assert_eq!(app.focused(), Some(&[][..]));
+        app.handle_event(&Event::key(KeyCode::Tab));
+        assert_eq!(app.focused(), Some(&[][..]), "на первой вкладке фокусироваться не на чем");
+        app.handle_event(&Event::key(KeyCode::End));
+        app.handle_event(&Event::key(KeyCode::Tab));
+        assert_eq!(app.focused(), Some(&[0, 0][..]));
+        assert!(app.transcript().contains("вкладки, Настройки"));
+    }
+}
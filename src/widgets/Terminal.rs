@@ -0,0 +1,189 @@
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::event::{Event, InputDecoder};
+use crate::widgets::layout::{Constraints, Rect, Size};
+use crate::widgets::Widget;
+
+/// Терминал в интерактивном режиме: сырой ввод, альтернативный экран, мышь.
+/// При удалении восстанавливает прежние настройки.
+pub struct Terminal {
+    saved: String,
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("stty завершился с ошибкой"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+impl Terminal {
+    pub fn enter() -> io::Result<Terminal> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        // альтернативный экран, скрытый курсор, отчёты мыши в формате SGR
+        print!("\x1b[?1049h\x1b[?25l\x1b[?1000h\x1b[?1006h");
+        io::stdout().flush()?;
+        Ok(Terminal { saved })
+    }
+
+    /// Поток событий со стандартного ввода; заканчивается вместе с вводом.
+    /// Первым приходит `Event::Resize` с текущим размером, дальше — при каждом его изменении.
+    pub fn events(&self) -> Events {
+        let (sender, events) = mpsc::channel();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let input = sender.clone();
+        let done = Arc::clone(&finished);
+        thread::spawn(move || {
+            let mut buffer = [0u8; 256];
+            let mut decoder = InputDecoder::new();
+            'read: while let Ok(read @ 1..) = io::stdin().read(&mut buffer) {
+                for event in decoder.feed(&buffer[..read]) {
+                    if input.send(event).is_err() {
+                        break 'read;
+                    }
+                }
+            }
+            done.store(true, Ordering::Relaxed);
+        });
+
+        // Сигнал SIGWINCH без libc не поймать, поэтому размер опрашивается.
+        thread::spawn(move || {
+            let mut last = None;
+            while !finished.load(Ordering::Relaxed) {
+                let size = terminal_size();
+                if size.is_some() && size != last {
+                    last = size;
+                    *SIZE.lock().unwrap_or_else(|error| error.into_inner()) = Some(size);
+                    if sender.send(Event::Resize(size.unwrap_or_default())).is_err() {
+                        return;
+                    }
+                }
+                thread::sleep(Duration::from_millis(200));
+            }
+        });
+        Events { events }
+    }
+}
+
+/// Размер терминала: `stty size`, а если терминала нет — переменные `COLUMNS` и `LINES`.
+pub fn terminal_size() -> Option<Size> {
+    let from_stty = stty(&["size"]).ok().and_then(|output| {
+        let (rows, columns) = output.split_once(' ')?;
+        Some(Size::new(columns.parse().ok()?, rows.parse().ok()?))
+    });
+    from_stty.filter(|size| size.width > 0 && size.height > 0).or_else(|| {
+        let variable = |name| std::env::var(name).ok()?.parse().ok();
+        Some(Size::new(variable("COLUMNS")?, variable("LINES")?))
+    })
+}
+
+/// Последний известный размер терминала; `None` — ещё не спрашивали.
+static SIZE: Mutex<Option<Option<Size>>> = Mutex::new(None);
+
+/// Размер терминала без запуска `stty` на каждый вызов: спрашивается один раз,
+/// а дальше обновляется потоком `Terminal::events` вместе с каждым `Event::Resize`.
+pub fn cached_terminal_size() -> Option<Size> {
+    *SIZE.lock().unwrap_or_else(|error| error.into_inner()).get_or_insert_with(terminal_size)
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?1006l\x1b[?1000l\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+pub struct Events {
+    events: Receiver<Event>,
+}
+
+impl Iterator for Events {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        self.events.recv().ok()
+    }
+}
+
+/// Перерисовка на месте в обычном режиме терминала, без альтернативного экрана:
+/// каждый кадр пишется поверх предыдущего, и экран не прокручивается.
+/// Подходит для вывода хода работы из скриптов сборки.
+pub struct LiveRegion {
+    /// Сколько строк занял предыдущий кадр.
+    height: usize,
+}
+
+impl LiveRegion {
+    pub fn new() -> LiveRegion {
+        LiveRegion { height: 0 }
+    }
+
+    /// Рисует кадр; курсор остаётся в начале строки под ним.
+    pub fn draw(&mut self, canvas: &Canvas, out: &mut dyn Write) -> io::Result<()> {
+        if self.height > 0 {
+            write!(out, "\x1b[{}F", self.height)?;
+        }
+        for line in canvas.to_string().lines() {
+            writeln!(out, "{}\x1b[K", line)?;
+        }
+        // остатки более высокого предыдущего кадра
+        if canvas.size().height < self.height {
+            write!(out, "\x1b[J")?;
+        }
+        self.height = canvas.size().height;
+        out.flush()
+    }
+
+    /// Рисует виджет в его собственном размере.
+    pub fn draw_widget(&mut self, widget: &dyn Widget, out: &mut dyn Write) -> io::Result<()> {
+        let area = Rect::from_size(widget.measure(Constraints::unbounded()));
+        let mut canvas = Canvas::new(area.size());
+        widget.render(&mut canvas, area);
+        self.draw(&canvas, out)
+    }
+
+    /// Печатает строку над кадром, например запись журнала; кадр надо нарисовать заново.
+    pub fn print_above(&mut self, text: &str, out: &mut dyn Write) -> io::Result<()> {
+        if self.height > 0 {
+            write!(out, "\x1b[{}F\x1b[J", self.height)?;
+            self.height = 0;
+        }
+        writeln!(out, "{}", text)?;
+        out.flush()
+    }
+}
+
+impl Default for LiveRegion {
+    fn default() -> LiveRegion {
+        LiveRegion::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Label;
+
+    #[test]
+    fn frames_overwrite_each_other() {
+        let mut live = LiveRegion::new();
+        let mut out = Vec::new();
+        live.draw_widget(&Label::new("1\n2"), &mut out).unwrap();
+        live.draw_widget(&Label::new("3"), &mut out).unwrap();
+        live.print_above("готово", &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1\x1b[K\n2\x1b[K\n\x1b[2F3\x1b[K\n\x1b[J\x1b[1F\x1b[Jготово\n"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::Value;
+use crate::widgets::edit_buffer::{EditBuffer, EditHooks};
+use crate::widgets::event::Event;
+use crate::widgets::layout::{Rect, Size};
+use crate::widgets::style::{Color, Style};
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Многострочное поле ввода; Enter переносит строку.
+pub struct TextArea {
+    buffer: EditBuffer,
+    size: Size,
+    placeholder: String,
+    style: Themed<Style>,
+    placeholder_style: Themed<Style>,
+    error_style: Themed<Style>,
+    focused: bool,
+    hooks: EditHooks,
+}
+
+impl TextArea {
+    pub fn new(width: usize, height: usize) -> TextArea {
+        TextArea {
+            buffer: EditBuffer::new(true),
+            size: Size::new(width, height),
+            placeholder: String::new(),
+            style: Themed::new(Style::default()),
+            placeholder_style: Themed::new(Style::new().dim()),
+            error_style: Themed::new(Style::new().fg(Color::Red)),
+            focused: false,
+            hooks: EditHooks::default(),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        self.buffer.text()
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.buffer.set_text(text);
+        self.hooks.validate(self.buffer.text());
+    }
+
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = placeholder.to_owned();
+    }
+
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.buffer.set_max_length(max_length);
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_placeholder_style(&mut self, style: Style) {
+        self.placeholder_style.set(style);
+    }
+
+    pub fn set_error_style(&mut self, style: Style) {
+        self.error_style.set(style);
+    }
+
+    pub fn set_validator(&mut self, validator: impl Fn(&str) -> Result<(), String> + 'static) {
+        self.hooks.set_validator(validator, self.buffer.text());
+    }
+
+    pub fn set_on_change(&mut self, on_change: impl FnMut(&str) + 'static) {
+        self.hooks.set_on_change(on_change);
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.hooks.error()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.hooks.error().is_none()
+    }
+
+    pub fn buffer(&self) -> &EditBuffer {
+        &self.buffer
+    }
+}
+
+impl Widget for TextArea {
+    fn width(&self) -> usize {
+        self.size.width
+    }
+
+    fn height(&self) -> usize {
+        self.size.height
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::TextBox, &self.placeholder).with_value(self.text())
+    }
+
+    /// Проверка и обработчик изменения — замыкания, они не сохраняются.
+    fn describe(&self) -> Option<Value> {
+        let mut properties = vec![
+            ("type", Value::from("text_area")),
+            ("width", Value::from(self.size.width)),
+            ("height", Value::from(self.size.height)),
+        ];
+        if !self.text().is_empty() {
+            properties.push(("text", Value::from(self.text())));
+        }
+        if !self.placeholder.is_empty() {
+            properties.push(("placeholder", Value::from(self.placeholder.as_str())));
+        }
+        if let Some(max_length) = self.buffer.max_length() {
+            properties.push(("max_length", Value::from(max_length)));
+        }
+        Some(Value::table(properties))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        if self.buffer.text().is_empty() && !self.placeholder.is_empty() {
+            let placeholder_style = self.placeholder_style.get();
+            canvas.fill(area, " ", placeholder_style);
+            for (y, line) in (area.y..area.bottom()).zip(self.placeholder.lines()) {
+                canvas.put_str(area.x, y, line, placeholder_style);
+            }
+            return;
+        }
+        let style = if self.hooks.error().is_some() { self.error_style.get() } else { self.style.get() };
+        self.buffer.render(canvas, area, style, None, self.focused);
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Label));
+        self.placeholder_style.theme(theme.style(Role::Label).dim());
+        self.error_style.theme(theme.style(Role::ErrorLabel));
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::Key(key) = event else {
+            return false;
+        };
+        self.hooks.handle_key(&mut self.buffer, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::event::KeyCode;
+    use crate::widgets::snapshot::render_sized;
+
+    #[test]
+    fn scrolls_vertically() {
+        let mut area = TextArea::new(5, 2);
+        for event in Event::text("один") {
+            area.handle_event(&event);
+        }
+        area.handle_event(&Event::key(KeyCode::Enter));
+        for event in Event::text("два\nтри") {
+            area.handle_event(&event);
+        }
+        assert_eq!(area.text(), "один\nдва\nтри");
+        let out = render_sized(&area, area.size_hint());
+        assert_eq!(out, "два  \nтри  \n");
+    }
+}
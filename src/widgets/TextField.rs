@@ -0,0 +1,225 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::Value;
+use crate::widgets::edit_buffer::{EditBuffer, EditHooks};
+use crate::widgets::event::Event;
+use crate::widgets::layout::Rect;
+use crate::widgets::style::{Color, Style};
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Однострочное поле ввода.
+pub struct TextField {
+    buffer: EditBuffer,
+    width: usize,
+    placeholder: String,
+    mask: Option<char>,
+    style: Themed<Style>,
+    placeholder_style: Themed<Style>,
+    error_style: Themed<Style>,
+    focused: bool,
+    hooks: EditHooks,
+}
+
+impl TextField {
+    pub fn new(width: usize) -> TextField {
+        TextField {
+            buffer: EditBuffer::new(false),
+            width,
+            placeholder: String::new(),
+            mask: None,
+            style: Themed::new(Style::new().underline()),
+            placeholder_style: Themed::new(Style::new().underline().dim()),
+            error_style: Themed::new(Style::new().underline().fg(Color::Red)),
+            focused: false,
+            hooks: EditHooks::default(),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        self.buffer.text()
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.buffer.set_text(text);
+        self.hooks.validate(self.buffer.text());
+    }
+
+    /// Подсказка, которая видна, пока поле пустое.
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = placeholder.to_owned();
+    }
+
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.buffer.set_max_length(max_length);
+    }
+
+    /// Поле пароля: каждый символ выводится как `*`.
+    pub fn set_password(&mut self, password: bool) {
+        self.mask = password.then_some('*');
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_placeholder_style(&mut self, style: Style) {
+        self.placeholder_style.set(style);
+    }
+
+    pub fn set_error_style(&mut self, style: Style) {
+        self.error_style.set(style);
+    }
+
+    pub fn set_validator(&mut self, validator: impl Fn(&str) -> Result<(), String> + 'static) {
+        self.hooks.set_validator(validator, self.buffer.text());
+    }
+
+    pub fn set_on_change(&mut self, on_change: impl FnMut(&str) + 'static) {
+        self.hooks.set_on_change(on_change);
+    }
+
+    /// Сообщение последней неудачной проверки.
+    pub fn error(&self) -> Option<&str> {
+        self.hooks.error()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.hooks.error().is_none()
+    }
+
+    pub fn buffer(&self) -> &EditBuffer {
+        &self.buffer
+    }
+}
+
+impl Widget for TextField {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+
+    fn accessible(&self) -> Accessible {
+        let value = match self.mask {
+            Some(_) if !self.text().is_empty() => "скрытый текст".to_owned(),
+            _ => self.text().to_owned(),
+        };
+        Accessible::new(AccessRole::TextBox, &self.placeholder).with_value(value)
+    }
+
+    /// Проверка и обработчик изменения — замыкания, они не сохраняются.
+    fn describe(&self) -> Option<Value> {
+        let mut properties = vec![("type", Value::from("text_field")), ("width", Value::from(self.width))];
+        if !self.text().is_empty() {
+            properties.push(("text", Value::from(self.text())));
+        }
+        if !self.placeholder.is_empty() {
+            properties.push(("placeholder", Value::from(self.placeholder.as_str())));
+        }
+        if let Some(max_length) = self.buffer.max_length() {
+            properties.push(("max_length", Value::from(max_length)));
+        }
+        if self.mask.is_some() {
+            properties.push(("password", Value::from(true)));
+        }
+        Some(Value::table(properties))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let area = Rect::new(area.x, area.y, area.width, area.height.min(1));
+        let style = if self.hooks.error().is_some() { self.error_style.get() } else { self.style.get() };
+        if self.buffer.text().is_empty() && !self.placeholder.is_empty() {
+            let placeholder_style = self.placeholder_style.get();
+            canvas.fill(area, " ", placeholder_style);
+            canvas.put_str(area.x, area.y, &self.placeholder, placeholder_style);
+            if self.focused {
+                canvas.set_style(Rect::new(area.x, area.y, 1, 1), Style { reverse: true, ..placeholder_style });
+            }
+            return;
+        }
+        self.buffer.render(canvas, area, style, self.mask, self.focused);
+    }
+
+    /// Подчёркивание остаётся при любой теме: по нему поле видно на экране.
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Label).underline());
+        self.placeholder_style.theme(theme.style(Role::Label).underline().dim());
+        self.error_style.theme(theme.style(Role::ErrorLabel).underline());
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::Key(key) = event else {
+            return false;
+        };
+        self.hooks.handle_key(&mut self.buffer, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::event::{KeyCode, KeyEvent};
+    use crate::widgets::snapshot::render;
+
+    fn type_text(field: &mut TextField, text: &str) {
+        for event in Event::text(text) {
+            field.handle_event(&event);
+        }
+    }
+
+    #[test]
+    fn placeholder_and_typing() {
+        let mut field = TextField::new(8);
+        field.set_style(Style::default());
+        field.set_placeholder("имя");
+        field.set_placeholder_style(Style::default());
+        assert_eq!(render(&field), "имя     \n");
+        type_text(&mut field, "Иван");
+        assert_eq!(field.text(), "Иван");
+        assert_eq!(render(&field), "Иван    \n");
+    }
+
+    #[test]
+    fn scrolls_to_cursor() {
+        let mut field = TextField::new(4);
+        field.set_style(Style::default());
+        type_text(&mut field, "abcdef");
+        assert_eq!(render(&field), "def \n");
+        field.handle_event(&Event::Key(KeyEvent::new(KeyCode::Home)));
+        assert_eq!(render(&field), "abcd\n");
+    }
+
+    #[test]
+    fn password_mask() {
+        let mut field = TextField::new(6);
+        field.set_style(Style::default());
+        field.set_password(true);
+        type_text(&mut field, "секрет");
+        assert_eq!(field.text(), "секрет");
+        // курсор стоит после текста, поэтому первая звёздочка ушла за левый край
+        assert_eq!(render(&field), "***** \n");
+    }
+
+    #[test]
+    fn validation_switches_style() {
+        let mut field = TextField::new(4);
+        field.set_validator(|text| text.parse::<u32>().map(|_| ()).map_err(|_| "нужно число".to_owned()));
+        assert_eq!(field.error(), Some("нужно число"));
+        type_text(&mut field, "42");
+        assert!(field.is_valid());
+        type_text(&mut field, "x");
+        assert_eq!(field.error(), Some("нужно число"));
+        assert!(render(&field).starts_with("␛[4;31m"));
+    }
+}
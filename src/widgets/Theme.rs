@@ -0,0 +1,473 @@
+use std::fs;
+use std::path::Path;
+use crate::widgets::border::BorderStyle;
+use crate::widgets::document::{Key, Kind, LoadError, Position, Value};
+use crate::widgets::style::{Color, Style};
+use crate::widgets::{json, toml};
+
+/// Назначение элемента оформления; тема сопоставляет каждой роли стиль.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    WindowTitle,
+    Border,
+    Button,
+    ButtonFocused,
+    Label,
+    ErrorLabel,
+    /// Выделенные данные: заполненная часть индикатора, кадр спиннера,
+    /// включённый переключатель, столбцы диаграмм.
+    Accent,
+}
+
+impl Role {
+    pub const ALL: [Role; 7] = [
+        Role::WindowTitle,
+        Role::Border,
+        Role::Button,
+        Role::ButtonFocused,
+        Role::Label,
+        Role::ErrorLabel,
+        Role::Accent,
+    ];
+
+    /// Имя роли в файле темы.
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::WindowTitle => "window_title",
+            Role::Border => "border",
+            Role::Button => "button",
+            Role::ButtonFocused => "button_focused",
+            Role::Label => "label",
+            Role::ErrorLabel => "error_label",
+            Role::Accent => "accent",
+        }
+    }
+}
+
+/// Набор стилей по ролям и рамок для окон и кнопок.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    styles: [Style; Role::ALL.len()],
+    pub window_border: BorderStyle,
+    pub button_border: BorderStyle,
+}
+
+impl Theme {
+    pub fn style(&self, role: Role) -> Style {
+        self.styles[role as usize]
+    }
+
+    pub fn set_style(&mut self, role: Role, style: Style) {
+        self.styles[role as usize] = style;
+    }
+
+    /// Копия темы с другим стилем одной роли.
+    pub fn with(mut self, role: Role, style: Style) -> Theme {
+        self.set_style(role, style);
+        self
+    }
+
+    /// Оформление по умолчанию: без цветов, фокус — инверсией.
+    pub fn monochrome() -> Theme {
+        let mut theme = Theme {
+            name: "monochrome".to_owned(),
+            styles: [Style::default(); Role::ALL.len()],
+            window_border: BorderStyle::ASCII,
+            button_border: BorderStyle::ASCII,
+        };
+        theme.set_style(Role::ButtonFocused, Style::new().reverse());
+        theme.set_style(Role::ErrorLabel, Style::new().bold().underline());
+        theme.set_style(Role::Accent, Style::new().bold());
+        theme
+    }
+
+    pub fn light() -> Theme {
+        Theme {
+            name: "light".to_owned(),
+            styles: [
+                Style::new().fg(Color::BrightWhite).bg(Color::Blue).bold(),
+                Style::new().fg(Color::Blue),
+                Style::new().fg(Color::Black).bg(Color::White),
+                Style::new().fg(Color::BrightWhite).bg(Color::Blue).bold(),
+                Style::new().fg(Color::Black),
+                Style::new().fg(Color::Red).bold(),
+                Style::new().fg(Color::Green),
+            ],
+            window_border: BorderStyle::LIGHT,
+            button_border: BorderStyle::ROUNDED,
+        }
+    }
+
+    pub fn dark() -> Theme {
+        Theme {
+            name: "dark".to_owned(),
+            styles: [
+                Style::new().fg(Color::Black).bg(Color::Cyan).bold(),
+                Style::new().fg(Color::Cyan),
+                Style::new().fg(Color::BrightWhite).bg(Color::Indexed(238)),
+                Style::new().fg(Color::Black).bg(Color::BrightCyan).bold(),
+                Style::new().fg(Color::White),
+                Style::new().fg(Color::BrightRed).bold(),
+                Style::new().fg(Color::BrightCyan),
+            ],
+            window_border: BorderStyle::ROUNDED,
+            button_border: BorderStyle::ROUNDED,
+        }
+    }
+
+    pub fn high_contrast() -> Theme {
+        Theme {
+            name: "high-contrast".to_owned(),
+            styles: [
+                Style::new().fg(Color::Black).bg(Color::BrightYellow).bold(),
+                Style::new().fg(Color::BrightYellow).bold(),
+                Style::new().fg(Color::BrightWhite).bg(Color::Black).bold(),
+                Style::new().fg(Color::Black).bg(Color::BrightYellow).bold().underline(),
+                Style::new().fg(Color::BrightWhite).bold(),
+                Style::new().fg(Color::BrightWhite).bg(Color::Red).bold(),
+                Style::new().fg(Color::BrightYellow).bold(),
+            ],
+            window_border: BorderStyle::DOUBLE,
+            button_border: BorderStyle::HEAVY,
+        }
+    }
+
+    /// Встроенная тема по имени.
+    pub fn named(name: &str) -> Option<Theme> {
+        match name {
+            "monochrome" => Some(Theme::monochrome()),
+            "light" => Some(Theme::light()),
+            "dark" => Some(Theme::dark()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Тема из файла `.toml` или `.json`.
+    pub fn load(path: &Path) -> Result<Theme, LoadError> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| LoadError::new(format!("{}: {}", path.display(), error), Position::default()))?;
+        if path.extension().is_some_and(|extension| extension == "json") {
+            Theme::from_value(&json::parse(&text)?)
+        } else {
+            Theme::from_value(&toml::parse(&text)?)
+        }
+    }
+
+    /// Описание темы:
+    ///
+    /// ```toml
+    /// name = "моя"
+    /// base = "dark"              # необязательно: от какой встроенной темы отталкиваться
+    ///
+    /// [styles]
+    /// window_title = { fg = "black", bg = "#ffaf00", bold = true }
+    ///
+    /// [borders]
+    /// window = "double"
+    /// button = "rounded"
+    /// ```
+    pub fn from_value(value: &Value) -> Result<Theme, LoadError> {
+        check_keys(value, &["name", "base", "styles", "borders"])?;
+        let mut theme = match value.get("base") {
+            None => Theme::monochrome(),
+            Some(base) => Theme::named(string(base)?).ok_or_else(|| LoadError::new("неизвестная встроенная тема", base.position))?,
+        };
+        if let Some(name) = value.get("name") {
+            theme.name = string(name)?.to_owned();
+        }
+        if let Some(styles) = value.get("styles") {
+            for (key, style) in table(styles)? {
+                let role = Role::ALL
+                    .into_iter()
+                    .find(|role| role.name() == key.name)
+                    .ok_or_else(|| LoadError::new(format!("неизвестная роль `{}`", key.name), key.position))?;
+                theme.set_style(role, parse_style(style)?);
+            }
+        }
+        if let Some(borders) = value.get("borders") {
+            check_keys(borders, &["window", "button"])?;
+            if let Some(border) = borders.get("window") {
+                theme.window_border = parse_border(border)?;
+            }
+            if let Some(border) = borders.get("button") {
+                theme.button_border = parse_border(border)?;
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Обратное к `from_value`; встроенные рамки записываются по имени.
+    pub fn to_value(&self) -> Value {
+        let styles = Role::ALL.iter().map(|&role| (role.name(), style_value(self.style(role)))).collect();
+        let mut borders = Vec::new();
+        for (name, border) in [("window", &self.window_border), ("button", &self.button_border)] {
+            if let Some(border) = border.name() {
+                borders.push((name, Value::from(border)));
+            }
+        }
+        Value::table(vec![
+            ("name", Value::from(self.name.as_str())),
+            ("styles", Value::table(styles)),
+            ("borders", Value::table(borders)),
+        ])
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::monochrome()
+    }
+}
+
+/// Значение, которое задаёт тема, пока виджет не переопределил его сам.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Themed<T> {
+    value: T,
+    custom: bool,
+}
+
+impl<T: Copy> Themed<T> {
+    pub fn new(value: T) -> Themed<T> {
+        Themed { value, custom: false }
+    }
+
+    pub fn get(&self) -> T {
+        self.value
+    }
+
+    /// Явная настройка виджета: темы её больше не меняют.
+    pub fn set(&mut self, value: T) {
+        self.value = value;
+        self.custom = true;
+    }
+
+    pub fn theme(&mut self, value: T) {
+        if !self.custom {
+            self.value = value;
+        }
+    }
+}
+
+const COLORS: [(&str, Color); 16] = [
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("white", Color::White),
+    ("bright_black", Color::BrightBlack),
+    ("bright_red", Color::BrightRed),
+    ("bright_green", Color::BrightGreen),
+    ("bright_yellow", Color::BrightYellow),
+    ("bright_blue", Color::BrightBlue),
+    ("bright_magenta", Color::BrightMagenta),
+    ("bright_cyan", Color::BrightCyan),
+    ("bright_white", Color::BrightWhite),
+];
+
+fn string(value: &Value) -> Result<&str, LoadError> {
+    match &value.kind {
+        Kind::String(text) => Ok(text),
+        _ => Err(LoadError::new("ожидалась строка", value.position)),
+    }
+}
+
+fn table(value: &Value) -> Result<&[(Key, Value)], LoadError> {
+    match &value.kind {
+        Kind::Table(entries) => Ok(entries),
+        _ => Err(LoadError::new("ожидалась таблица", value.position)),
+    }
+}
+
+fn check_keys(value: &Value, allowed: &[&str]) -> Result<(), LoadError> {
+    match table(value)?.iter().find(|(key, _)| !allowed.contains(&key.name.as_str())) {
+        Some((key, _)) => Err(LoadError::new(format!("неизвестный ключ `{}`", key.name), key.position)),
+        None => Ok(()),
+    }
+}
+
+/// Цвет по имени (`"red"`, `"bright_blue"`), номер палитры (`208`) или `"#rrggbb"`.
+fn parse_color(value: &Value) -> Result<Color, LoadError> {
+    let error = || LoadError::new("ожидался цвет: имя, номер 0–255 или #rrggbb", value.position);
+    match &value.kind {
+        Kind::Integer(n) => u8::try_from(*n).map(Color::Indexed).map_err(|_| error()),
+        Kind::String(text) => {
+            if let Some(hex) = text.strip_prefix('#').filter(|hex| hex.len() == 6) {
+                let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| error());
+                return Ok(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+            }
+            COLORS.iter().find(|(name, _)| name == text).map(|(_, color)| *color).ok_or_else(error)
+        }
+        _ => Err(error()),
+    }
+}
+
+fn color_value(color: Color) -> Value {
+    match color {
+        Color::Indexed(n) => Value::from(n as usize),
+        Color::Rgb(r, g, b) => Value::from(format!("#{:02x}{:02x}{:02x}", r, g, b).as_str()),
+        named => Value::from(COLORS.iter().find(|(_, color)| *color == named).map(|(name, _)| *name).unwrap_or_default()),
+    }
+}
+
+const ATTRIBUTES: [&str; 6] = ["bold", "dim", "italic", "underline", "reverse", "strikethrough"];
+
+fn attributes(style: &mut Style) -> [&mut bool; 6] {
+    [&mut style.bold, &mut style.dim, &mut style.italic, &mut style.underline, &mut style.reverse, &mut style.strikethrough]
+}
+
+fn parse_style(value: &Value) -> Result<Style, LoadError> {
+    let mut style = Style::default();
+    for (key, item) in table(value)? {
+        match key.name.as_str() {
+            "fg" => style.fg = Some(parse_color(item)?),
+            "bg" => style.bg = Some(parse_color(item)?),
+            name => {
+                let index = ATTRIBUTES
+                    .iter()
+                    .position(|attribute| *attribute == name)
+                    .ok_or_else(|| LoadError::new(format!("неизвестное свойство стиля `{}`", name), key.position))?;
+                let Kind::Boolean(flag) = item.kind else {
+                    return Err(LoadError::new(format!("`{}` должно быть true или false", name), item.position));
+                };
+                *attributes(&mut style)[index] = flag;
+            }
+        }
+    }
+    Ok(style)
+}
+
+fn style_value(mut style: Style) -> Value {
+    let mut properties = Vec::new();
+    if let Some(fg) = style.fg {
+        properties.push(("fg", color_value(fg)));
+    }
+    if let Some(bg) = style.bg {
+        properties.push(("bg", color_value(bg)));
+    }
+    for (name, flag) in ATTRIBUTES.into_iter().zip(attributes(&mut style)) {
+        if *flag {
+            properties.push((name, Value::from(true)));
+        }
+    }
+    Value::table(properties)
+}
+
+fn parse_border(value: &Value) -> Result<BorderStyle, LoadError> {
+    let name = string(value)?;
+    BorderStyle::named(name).ok_or_else(|| LoadError::new(format!("неизвестная рамка `{}`", name), value.position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::{
+        Button, Canvas, Checkbox, Gauge, Label, ProgressBar, RadioGroup, Rect, Spinner, Table, TextField, Widget, Window,
+    };
+
+    #[test]
+    fn file_round_trip() {
+        let text = "name = \"закат\"\nbase = \"dark\"\n\n[styles]\nbutton = { fg = 208, bg = \"#1c1c1c\", bold = true }\n\n[borders]\nwindow = \"double\"\n";
+        let theme = Theme::from_value(&toml::parse(text).unwrap()).unwrap();
+        assert_eq!(theme.style(Role::Button), Style::new().fg(Color::Indexed(208)).bg(Color::Rgb(28, 28, 28)).bold());
+        assert_eq!(theme.style(Role::Label), Theme::dark().style(Role::Label));
+        assert_eq!(theme.window_border, BorderStyle::DOUBLE);
+        let written = toml::write(&theme.to_value());
+        assert_eq!(Theme::from_value(&toml::parse(&written).unwrap()).unwrap(), theme);
+
+        let error = Theme::from_value(&toml::parse("[styles]\nlabel = { fg = \"rose\" }\n").unwrap()).unwrap_err();
+        assert_eq!(error.position, Position { line: 2, column: 16 });
+    }
+
+    #[test]
+    fn applied_top_down_with_overrides() {
+        let mut custom = Button::new("Своя");
+        custom.set_style(Style::new().fg(Color::Magenta));
+        let mut inner = Window::new("Внутри");
+        inner.set_theme(Theme::monochrome());
+        inner.add_widget(Box::new(Label::new("моно")));
+        let mut window = Window::new("Окно");
+        window.add_widget(Box::new(Label::new("текст")));
+        window.add_widget(Box::new(custom));
+        window.add_widget(Box::new(inner));
+        window.set_theme(Theme::high_contrast());
+
+        let theme = Theme::high_contrast();
+        let area = Rect::from_size(window.size_hint());
+        let mut canvas = Canvas::new(area.size());
+        window.render(&mut canvas, area);
+        let cell = |x, y| canvas.get(x, y).unwrap();
+        assert_eq!((cell(0, 0).symbol.as_str(), cell(0, 0).style), ("╔", theme.style(Role::Border)));
+        assert_eq!(cell(2, 1).style, theme.style(Role::WindowTitle));
+        assert_eq!(cell(2, 3).style, theme.style(Role::Label));
+        // кнопка взяла рамку из темы, но сохранила свой стиль
+        assert_eq!((cell(2, 4).symbol.as_str(), cell(3, 5).style), ("┏", Style::new().fg(Color::Magenta)));
+        // вложенное окно осталось в своей теме
+        assert_eq!((cell(2, 7).symbol.as_str(), cell(2, 7).style), ("+", Style::default()));
+        assert_eq!(cell(4, 10).style, Style::default());
+    }
+
+    #[test]
+    fn reaches_form_controls_and_table_cells() {
+        let theme = Theme::high_contrast();
+        let mut table = Table::new(&["Опция"]);
+        table.add_row(vec![Box::new(Checkbox::new("звук"))]);
+        let mut field = TextField::new(4);
+        field.set_validator(|_| Err("ошибка".to_owned()));
+        let mut group = RadioGroup::new("", &["да"]);
+        group.set_focused(true);
+        let mut window = Window::new("Окно");
+        window.add_widget(Box::new(table));
+        window.add_widget(Box::new(field));
+        window.add_widget(Box::new(group));
+        window.set_theme(theme.clone());
+
+        let render = |widget: &dyn Widget| {
+            let area = Rect::from_size(widget.size_hint());
+            let mut canvas = Canvas::new(area.size());
+            widget.render(&mut canvas, area);
+            canvas
+        };
+        let children = window.children();
+        let table = render(children[0]);
+        assert_eq!((table.get(0, 0).unwrap().symbol.as_str(), table.get(0, 0).unwrap().style), ("╔", theme.style(Role::Border)));
+        assert_eq!(table.get(2, 3).unwrap().style, theme.style(Role::Label), "флажок в ячейке");
+        assert_eq!(render(children[1]).get(0, 0).unwrap().style, theme.style(Role::ErrorLabel).underline());
+        let group = render(children[2]);
+        assert_eq!(group.get(0, 0).unwrap().symbol, "╔");
+        assert_eq!(group.get(2, 1).unwrap().style, theme.style(Role::ButtonFocused));
+    }
+
+    #[test]
+    fn indicators_take_accent() {
+        let theme = Theme::dark();
+        let mut bar = ProgressBar::new(16, 2);
+        bar.set_done(1);
+        let mut spinner = Spinner::new("ждём");
+        let mut gauge = Gauge::new(4);
+        gauge.set_ratio(0.5);
+        let mut window = Window::new("");
+        window.add_widget(Box::new(bar));
+        window.add_widget(Box::new(spinner));
+        window.add_widget(Box::new(gauge));
+        window.apply_theme(&theme);
+
+        let mut canvas = Canvas::new(window.size_hint());
+        window.render(&mut canvas, Rect::from_size(window.size_hint()));
+        let style = |x, y| canvas.get(x, y).unwrap().style;
+        assert_eq!((style(4, 3), style(9, 3)), (theme.style(Role::Accent), theme.style(Role::Label)), "полоса");
+        assert_eq!((style(2, 4), style(4, 4)), (theme.style(Role::Accent), theme.style(Role::Label)), "спиннер");
+        assert_eq!((style(2, 5), style(5, 5)), (theme.style(Role::ButtonFocused), theme.style(Role::Label)), "шкала");
+
+        spinner = Spinner::new("");
+        spinner.set_frame_style(Style::new().fg(Color::Magenta));
+        spinner.apply_theme(&theme);
+        let mut canvas = Canvas::new(spinner.size_hint());
+        spinner.render(&mut canvas, Rect::from_size(spinner.size_hint()));
+        assert_eq!(canvas.get(0, 0).unwrap().style, Style::new().fg(Color::Magenta));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::Value;
+use crate::widgets::event::{Event, KeyCode, MouseButton, MouseKind};
+use crate::widgets::layout::{fit, text_width, Align, Rect};
+use crate::widgets::style::{Color, Style};
+use crate::widgets::theme::{Role, Theme, Themed};
+use crate::widgets::Widget;
+
+/// Переключатель `надпись [ ВКЛ ]`. В отличие от флажка, состояние подписано
+/// словами, а включённое положение выделено цветом.
+pub struct Toggle {
+    label: String,
+    on: bool,
+    on_text: String,
+    off_text: String,
+    style: Themed<Style>,
+    on_style: Themed<Style>,
+    focus_style: Themed<Style>,
+    focused: bool,
+    on_change: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Toggle {
+    pub fn new(label: &str) -> Toggle {
+        Toggle {
+            label: label.to_owned(),
+            on: false,
+            on_text: "ON".to_owned(),
+            off_text: "OFF".to_owned(),
+            style: Themed::new(Style::default()),
+            on_style: Themed::new(Style::new().fg(Color::Green).bold()),
+            focus_style: Themed::new(Style::new().reverse()),
+            focused: false,
+            on_change: None,
+        }
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// Меняет состояние без вызова обработчика.
+    pub fn set_on(&mut self, on: bool) {
+        self.on = on;
+    }
+
+    /// Подписи включённого и выключенного положения.
+    pub fn set_texts(&mut self, on: &str, off: &str) {
+        self.on_text = on.to_owned();
+        self.off_text = off.to_owned();
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    pub fn set_on_style(&mut self, style: Style) {
+        self.on_style.set(style);
+    }
+
+    pub fn set_focus_style(&mut self, style: Style) {
+        self.focus_style.set(style);
+    }
+
+    pub fn set_on_change(&mut self, on_change: impl FnMut(bool) + 'static) {
+        self.on_change = Some(Box::new(on_change));
+    }
+
+    pub fn toggle(&mut self) {
+        self.on = !self.on;
+        if let Some(on_change) = &mut self.on_change {
+            on_change(self.on);
+        }
+    }
+
+    fn switch_width(&self) -> usize {
+        text_width(&self.on_text).max(text_width(&self.off_text)) + 4
+    }
+}
+
+impl Widget for Toggle {
+    fn width(&self) -> usize {
+        let label = text_width(&self.label);
+        if label == 0 {
+            self.switch_width()
+        } else {
+            label + 1 + self.switch_width()
+        }
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::Switch, &self.label).with_value(if self.on { &self.on_text } else { &self.off_text }.as_str())
+    }
+
+    fn describe(&self) -> Option<Value> {
+        let mut properties = vec![("type", Value::from("toggle")), ("label", Value::from(self.label.as_str()))];
+        if self.on {
+            properties.push(("on", Value::from(true)));
+        }
+        if (self.on_text.as_str(), self.off_text.as_str()) != ("ON", "OFF") {
+            properties.push(("on_text", Value::from(self.on_text.as_str())));
+            properties.push(("off_text", Value::from(self.off_text.as_str())));
+        }
+        Some(Value::table(properties))
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        let mut x = area.x;
+        if !self.label.is_empty() {
+            x += canvas.put_str(x, area.y, &self.label, self.style.get()) + 1;
+        }
+        let (text, state_style) = if self.on { (&self.on_text, self.on_style.get()) } else { (&self.off_text, self.style.get()) };
+        let style = if self.focused { self.focus_style.get() } else { self.style.get() };
+        let inner = self.switch_width() - 4;
+        canvas.put_str(x, area.y, "[ ", style);
+        canvas.put_str(x + 2, area.y, &fit(text, inner, Align::Left), if self.focused { style } else { state_style });
+        canvas.put_str(x + 2 + inner, area.y, " ]", style);
+    }
+
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.style.theme(theme.style(Role::Label));
+        self.on_style.theme(theme.style(Role::Accent));
+        self.focus_style.theme(theme.style(Role::ButtonFocused));
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let pressed = match event {
+            Event::Key(key) => key.is(KeyCode::Enter) || key.is(KeyCode::Char(' ')),
+            Event::Mouse(mouse) => mouse.kind == MouseKind::Press(MouseButton::Left),
+            Event::Resize(_) => false,
+        };
+        let before = self.on;
+        if pressed {
+            self.toggle();
+        }
+        self.on != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::render;
+
+    #[test]
+    fn switches_text() {
+        let mut toggle = Toggle::new("Wi-Fi");
+        toggle.set_on_style(Style::default());
+        assert_eq!(render(&toggle), "Wi-Fi [ OFF ]\n");
+        toggle.handle_event(&Event::key(KeyCode::Enter));
+        assert!(toggle.is_on());
+        assert_eq!(render(&toggle), "Wi-Fi [ ON  ]\n");
+    }
+}
@@ -0,0 +1,406 @@
+use crate::widgets::document::{Key, Kind, LoadError, Position, Reader, Value};
+use crate::widgets::json::quote;
+
+type Table = Vec<(Key, Value)>;
+
+/// Разбирает TOML-документ: пары `ключ = значение` (в том числе составные ключи),
+/// заголовки `[таблица]` и `[[массив.таблиц]]`, строки всех четырёх видов, целые числа,
+/// логические значения, массивы и встроенные таблицы. Даты и дробные числа не поддерживаются.
+pub fn parse(text: &str) -> Result<Value, LoadError> {
+    let mut reader = Reader::new(text);
+    let mut root = Table::new();
+    let mut current: Vec<Key> = Vec::new();
+    loop {
+        skip_blank(&mut reader);
+        match reader.peek() {
+            None => break,
+            Some('\n' | '\r' | '#') => {}
+            Some('[') => {
+                let position = reader.position();
+                reader.next();
+                let array = reader.eat('[');
+                let path = parse_path(&mut reader)?;
+                reader.expect(']')?;
+                if array {
+                    reader.expect(']')?;
+                    push_array_table(&mut root, &path, position)?;
+                } else {
+                    table_at(&mut root, &path)?;
+                }
+                current = path;
+            }
+            Some(_) => {
+                let path = parse_path(&mut reader)?;
+                skip_blank(&mut reader);
+                reader.expect('=')?;
+                skip_blank(&mut reader);
+                let value = parse_value(&mut reader)?;
+                let (key, prefix) = path.split_last().unwrap();
+                let table = table_at(&mut root, &[current.as_slice(), prefix].concat())?;
+                insert(table, key.clone(), value)?;
+            }
+        }
+        end_of_line(&mut reader)?;
+    }
+    Ok(Value::new(Kind::Table(root), Position { line: 1, column: 1 }))
+}
+
+fn skip_blank(reader: &mut Reader) {
+    reader.skip_while(|c| c == ' ' || c == '\t');
+}
+
+/// После значения в строке допустим только комментарий.
+fn end_of_line(reader: &mut Reader) -> Result<(), LoadError> {
+    skip_blank(reader);
+    if reader.peek() == Some('#') {
+        reader.skip_while(|c| c != '\n');
+    }
+    reader.eat('\r');
+    match reader.peek() {
+        None => Ok(()),
+        Some('\n') => {
+            reader.next();
+            Ok(())
+        }
+        Some(_) => Err(reader.error("после значения ожидался конец строки")),
+    }
+}
+
+/// Пробелы, переводы строк и комментарии внутри массивов.
+fn skip_space(reader: &mut Reader) {
+    loop {
+        reader.skip_while(|c| matches!(c, ' ' | '\t' | '\r' | '\n'));
+        if reader.peek() != Some('#') {
+            break;
+        }
+        reader.skip_while(|c| c != '\n');
+    }
+}
+
+fn is_bare(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Ключ, возможно составной: `a.b."в кавычках"`.
+fn parse_path(reader: &mut Reader) -> Result<Vec<Key>, LoadError> {
+    let mut path = Vec::new();
+    loop {
+        skip_blank(reader);
+        let position = reader.position();
+        let name = match reader.peek() {
+            Some('"') => parse_basic(reader)?,
+            Some('\'') => parse_literal(reader)?,
+            Some(c) if is_bare(c) => {
+                let mut name = String::new();
+                while let Some(c) = reader.peek().filter(|&c| is_bare(c)) {
+                    name.push(c);
+                    reader.next();
+                }
+                name
+            }
+            _ => return Err(reader.unexpected("ключ")),
+        };
+        path.push(Key { name, position });
+        skip_blank(reader);
+        if !reader.eat('.') {
+            return Ok(path);
+        }
+    }
+}
+
+/// Таблица по пути от `table`; недостающие создаются, из массивов таблиц берётся последняя.
+fn table_at<'a>(table: &'a mut Table, path: &[Key]) -> Result<&'a mut Table, LoadError> {
+    let Some((key, rest)) = path.split_first() else {
+        return Ok(table);
+    };
+    let index = match table.iter().position(|(other, _)| other.name == key.name) {
+        Some(index) => index,
+        None => {
+            table.push((key.clone(), Value::new(Kind::Table(Vec::new()), key.position)));
+            table.len() - 1
+        }
+    };
+    let next = match &mut table[index].1.kind {
+        Kind::Table(entries) => entries,
+        Kind::Array(items) => match items.last_mut() {
+            Some(Value { kind: Kind::Table(entries), .. }) => entries,
+            _ => return Err(LoadError::new(format!("`{}` — массив, а не таблица", key.name), key.position)),
+        },
+        _ => return Err(LoadError::new(format!("`{}` уже задан и не является таблицей", key.name), key.position)),
+    };
+    table_at(next, rest)
+}
+
+fn push_array_table(root: &mut Table, path: &[Key], position: Position) -> Result<(), LoadError> {
+    let (key, prefix) = path.split_last().unwrap();
+    let table = table_at(root, prefix)?;
+    let item = Value::new(Kind::Table(Vec::new()), position);
+    match table.iter_mut().find(|(other, _)| other.name == key.name) {
+        None => table.push((key.clone(), Value::new(Kind::Array(vec![item]), key.position))),
+        Some((_, Value { kind: Kind::Array(items), .. })) => items.push(item),
+        Some(_) => return Err(LoadError::new(format!("`{}` уже задан и не является массивом таблиц", key.name), key.position)),
+    }
+    Ok(())
+}
+
+fn insert(table: &mut Table, key: Key, value: Value) -> Result<(), LoadError> {
+    if table.iter().any(|(other, _)| other.name == key.name) {
+        return Err(LoadError::new(format!("ключ `{}` повторяется", key.name), key.position));
+    }
+    table.push((key, value));
+    Ok(())
+}
+
+fn parse_value(reader: &mut Reader) -> Result<Value, LoadError> {
+    let position = reader.position();
+    let kind = match reader.peek() {
+        Some('"') => Kind::String(parse_basic(reader)?),
+        Some('\'') => Kind::String(parse_literal(reader)?),
+        Some('[') => {
+            reader.next();
+            let mut items = Vec::new();
+            loop {
+                skip_space(reader);
+                if reader.eat(']') {
+                    break;
+                }
+                items.push(parse_value(reader)?);
+                skip_space(reader);
+                if reader.eat(']') {
+                    break;
+                }
+                reader.expect(',')?;
+            }
+            Kind::Array(items)
+        }
+        Some('{') => {
+            reader.next();
+            let mut entries = Table::new();
+            skip_blank(reader);
+            if !reader.eat('}') {
+                loop {
+                    let path = parse_path(reader)?;
+                    skip_blank(reader);
+                    reader.expect('=')?;
+                    skip_blank(reader);
+                    let value = parse_value(reader)?;
+                    let (key, prefix) = path.split_last().unwrap();
+                    insert(table_at(&mut entries, prefix)?, key.clone(), value)?;
+                    skip_blank(reader);
+                    if reader.eat('}') {
+                        break;
+                    }
+                    reader.expect(',')?;
+                    skip_blank(reader);
+                }
+            }
+            Kind::Table(entries)
+        }
+        Some(c) if c == '+' || c == '-' || c.is_ascii_digit() => {
+            let mut digits = String::new();
+            while let Some(c) = reader.peek().filter(|&c| c == '+' || c == '-' || c == '_' || c.is_ascii_digit()) {
+                if c != '_' {
+                    digits.push(c);
+                }
+                reader.next();
+            }
+            if matches!(reader.peek(), Some('.' | 'e' | 'E' | ':' | 'T')) {
+                return Err(reader.error("поддерживаются только целые числа"));
+            }
+            Kind::Integer(digits.parse().map_err(|_| LoadError::new("неверное число", position))?)
+        }
+        Some(c) if c.is_ascii_alphabetic() => {
+            let mut word = String::new();
+            while let Some(c) = reader.peek().filter(|c| c.is_ascii_alphabetic()) {
+                word.push(c);
+                reader.next();
+            }
+            match word.as_str() {
+                "true" => Kind::Boolean(true),
+                "false" => Kind::Boolean(false),
+                _ => return Err(LoadError::new(format!("неизвестное значение `{}`", word), position)),
+            }
+        }
+        _ => return Err(reader.unexpected("значение")),
+    };
+    Ok(Value::new(kind, position))
+}
+
+/// Строка в двойных кавычках, в том числе многострочная `"""…"""`, где `\`
+/// в конце строки склеивает её со следующей, съедая пробелы и переводы строк.
+fn parse_basic(reader: &mut Reader) -> Result<String, LoadError> {
+    let multiline = reader.looking_at("\"\"\"");
+    let quote = if multiline { "\"\"\"" } else { "\"" };
+    for _ in 0..quote.len() {
+        reader.next();
+    }
+    if multiline {
+        // перевод строки сразу после открывающих кавычек не входит в текст
+        reader.eat('\r');
+        reader.eat('\n');
+    }
+    let mut text = String::new();
+    loop {
+        if reader.looking_at(quote) {
+            for _ in 0..quote.len() {
+                reader.next();
+            }
+            return Ok(text);
+        }
+        let position = reader.position();
+        match reader.next() {
+            Some('\\') if multiline && line_ending_backslash(reader) => {
+                reader.skip_while(|c| c.is_whitespace());
+            }
+            Some('\\') => {
+                let c = match reader.next() {
+                    Some('"') => Some('"'),
+                    Some('\\') => Some('\\'),
+                    Some('b') => Some('\u{8}'),
+                    Some('f') => Some('\u{c}'),
+                    Some('n') => Some('\n'),
+                    Some('r') => Some('\r'),
+                    Some('t') => Some('\t'),
+                    Some('u') => char::from_u32(reader.hex_escape(4)?),
+                    Some('U') => char::from_u32(reader.hex_escape(8)?),
+                    _ => return Err(LoadError::new("неизвестная escape-последовательность", position)),
+                }
+                .ok_or_else(|| LoadError::new("неверный код символа", position))?;
+                text.push(c);
+            }
+            Some('\n') if !multiline => return Err(LoadError::new("строка не закрыта", position)),
+            Some(c) => text.push(c),
+            None => return Err(reader.error("строка не закрыта")),
+        }
+    }
+}
+
+/// После `\` до конца строки только пробелы.
+fn line_ending_backslash(reader: &Reader) -> bool {
+    matches!(reader.rest().iter().find(|&&c| c != ' ' && c != '\t'), Some('\n' | '\r'))
+}
+
+/// Строка в одинарных кавычках без экранирования, в том числе `'''…'''`.
+fn parse_literal(reader: &mut Reader) -> Result<String, LoadError> {
+    let multiline = reader.looking_at("'''");
+    let quote = if multiline { "'''" } else { "'" };
+    for _ in 0..quote.len() {
+        reader.next();
+    }
+    if multiline {
+        reader.eat('\r');
+        reader.eat('\n');
+    }
+    let mut text = String::new();
+    while !reader.looking_at(quote) {
+        match reader.next() {
+            Some('\n') if !multiline => return Err(reader.error("строка не закрыта")),
+            Some(c) => text.push(c),
+            None => return Err(reader.error("строка не закрыта")),
+        }
+    }
+    for _ in 0..quote.len() {
+        reader.next();
+    }
+    Ok(text)
+}
+
+/// Записывает документ: сначала простые ключи, затем вложенные таблицы
+/// и массивы таблиц в виде заголовков `[…]` и `[[…]]`.
+pub fn write(value: &Value) -> String {
+    let mut out = String::new();
+    if let Kind::Table(entries) = &value.kind {
+        write_table(entries, &mut Vec::new(), &mut out);
+    }
+    out
+}
+
+fn is_table_array(value: &Value) -> bool {
+    match &value.kind {
+        Kind::Array(items) => !items.is_empty() && items.iter().all(|item| matches!(item.kind, Kind::Table(_))),
+        _ => false,
+    }
+}
+
+fn write_key(name: &str) -> String {
+    if !name.is_empty() && name.chars().all(is_bare) {
+        name.to_owned()
+    } else {
+        quote(name)
+    }
+}
+
+fn write_table(entries: &Table, path: &mut Vec<String>, out: &mut String) {
+    for (key, value) in entries {
+        if !matches!(value.kind, Kind::Table(_)) && !is_table_array(value) {
+            out.push_str(&format!("{} = {}\n", write_key(&key.name), inline(value)));
+        }
+    }
+    for (key, value) in entries {
+        path.push(write_key(&key.name));
+        match &value.kind {
+            Kind::Table(nested) => {
+                out.push_str(&format!("\n[{}]\n", path.join(".")));
+                write_table(nested, path, out);
+            }
+            Kind::Array(items) if is_table_array(value) => {
+                for item in items {
+                    out.push_str(&format!("\n[[{}]]\n", path.join(".")));
+                    if let Kind::Table(nested) = &item.kind {
+                        write_table(nested, path, out);
+                    }
+                }
+            }
+            _ => {}
+        }
+        path.pop();
+    }
+}
+
+fn inline(value: &Value) -> String {
+    match &value.kind {
+        Kind::String(text) => quote(text),
+        Kind::Integer(n) => n.to_string(),
+        Kind::Boolean(flag) => flag.to_string(),
+        Kind::Array(items) => format!("[{}]", items.iter().map(inline).collect::<Vec<_>>().join(", ")),
+        Kind::Table(entries) if entries.is_empty() => "{}".to_owned(),
+        Kind::Table(entries) => {
+            let entries: Vec<String> =
+                entries.iter().map(|(key, value)| format!("{} = {}", write_key(&key.name), inline(value))).collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_and_arrays() {
+        let text = "# экран\nname = 'окно' # комментарий\nsize.width = 1_000\n\n[[items]]\nflags = [\n  true,\n  false, # нет\n]\n\n[[items]]\npoint = { x = -1, y = 2 }\ntext = \"\"\"\nдве\\tстроки\nтекста\"\"\"\n";
+        let value = parse(text).unwrap();
+        let items = value.get("items").unwrap();
+        let Kind::Array(items) = &items.kind else { panic!() };
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].get("text").unwrap().kind, Kind::String("две\tстроки\nтекста".to_owned()));
+        assert_eq!(value.get("size").unwrap().get("width").unwrap().kind, Kind::Integer(1000));
+        let written = write(&value);
+        assert_eq!(
+            written,
+            "name = \"окно\"\n\n[size]\nwidth = 1000\n\n[[items]]\nflags = [true, false]\n\n[[items]]\ntext = \"две\\tстроки\\nтекста\"\n\n[items.point]\nx = -1\ny = 2\n"
+        );
+        assert_eq!(write(&parse(&written).unwrap()), written);
+        let joined = parse("text = \"\"\"\nодна \\  \n\n    строка\"\"\"\n").unwrap();
+        assert_eq!(joined.get("text").unwrap().kind, Kind::String("одна строка".to_owned()));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let error = parse("a = 1\nb = 2 3\n").unwrap_err();
+        assert_eq!(error.to_string(), "строка 2, столбец 7: после значения ожидался конец строки");
+        assert_eq!(parse("a = 1\na = 2\n").unwrap_err().position, Position { line: 2, column: 1 });
+        assert_eq!(parse("a = \"обрыв\n").unwrap_err().message, "строка не закрыта");
+        assert_eq!(parse("a = 1\n[a]\n").unwrap_err().message, "`a` уже задан и не является таблицей");
+    }
+}
@@ -0,0 +1,95 @@
+/// Декларативное описание дерева виджетов. Каждый узел — `Тип { свойства; дети }`:
+///
+/// ```ignore
+/// ui! {
+///     Window { title: "Вход", size: (40, 10);
+///         Label { text: "Имя пользователя" },
+///         HBox { spacing: 1;
+///             Button { label: "OK" },
+///             (Button::new("Отмена")),
+///         },
+///     }
+/// }
+/// ```
+///
+/// Узел раскрывается в `Тип::builder().свойство(значение)….child(…).build()`,
+/// поэтому опечатка в имени свойства или значение не того типа — ошибка компиляции.
+/// Типы ищутся в месте вызова, их нужно импортировать. Готовый виджет можно
+/// вставить ребёнком, взяв выражение в скобки.
+#[macro_export]
+macro_rules! ui {
+    ($kind:ident { $($body:tt)* }) => {
+        $crate::ui!(@props $kind::builder(); $($body)*)
+    };
+
+    // свойства до `;`
+    (@props $builder:expr; $property:ident : $value:expr , $($rest:tt)*) => {
+        $crate::ui!(@props $builder.$property($value); $($rest)*)
+    };
+    (@props $builder:expr; $property:ident : $value:expr ; $($rest:tt)*) => {
+        $crate::ui!(@children $builder.$property($value); $($rest)*)
+    };
+    (@props $builder:expr; $property:ident : $value:expr) => {
+        $builder.$property($value).build()
+    };
+    (@props $builder:expr; ; $($rest:tt)*) => {
+        $crate::ui!(@children $builder; $($rest)*)
+    };
+    (@props $builder:expr; $($rest:tt)*) => {
+        $crate::ui!(@children $builder; $($rest)*)
+    };
+
+    // дочерние узлы через запятую
+    (@children $builder:expr; $kind:ident { $($body:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::ui!(@children $builder.child($crate::ui!($kind { $($body)* })); $($($rest)*)?)
+    };
+    (@children $builder:expr; ($child:expr) $(, $($rest:tt)*)?) => {
+        $crate::ui!(@children $builder.child($child); $($($rest)*)?)
+    };
+    (@children $builder:expr;) => {
+        $builder.build()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::widgets::*;
+    use crate::widgets::snapshot::render;
+
+    #[test]
+    fn macro_matches_builder() {
+        let built = Window::builder()
+            .title("Вход")
+            .child(Label::builder().text("Имя пользователя").build())
+            .child(
+                HBox::builder()
+                    .spacing(1)
+                    .child(Button::builder().label("OK").build())
+                    .child(Button::new("Отмена"))
+                    .build(),
+            )
+            .build();
+
+        let declared = crate::ui! {
+            Window { title: "Вход";
+                Label { text: "Имя пользователя" },
+                HBox { spacing: 1;
+                    Button { label: "OK" },
+                    (Button::new("Отмена")),
+                },
+            }
+        };
+        assert_eq!(render(&declared), render(&built));
+        assert_eq!(declared.children().len(), 2);
+    }
+
+    #[test]
+    fn leaf_and_empty_nodes() {
+        let label = crate::ui!(Label { text: "абзац текста", wrap: Wrap::Word, max_width: 6 });
+        assert_eq!(render(&label), "абзац \nтекста\n");
+        let column = crate::ui!(VBox {});
+        assert_eq!(column.children().len(), 0);
+        let window = crate::ui!(Window { size: (10, 5), border: BorderStyle::DOUBLE });
+        assert_eq!(window.size_hint(), Size::new(10, 5));
+    }
+}
@@ -0,0 +1,279 @@
+// Ширина текста в столбцах терминала с учётом графемных кластеров
+// и восточноазиатской ширины (упрощённые UAX #29 и UAX #11).
+
+/// Символы, которые присоединяются к предыдущему и не занимают места:
+/// комбинируемые диакритики, селекторы вариантов, модификаторы тона кожи, теги.
+const EXTEND: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2),
+    (0x05C4, 0x05C5),
+    (0x05C7, 0x05C7),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DC),
+    (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8),
+    (0x06EA, 0x06ED),
+    (0x0900, 0x0903),
+    (0x093A, 0x094F),
+    (0x0951, 0x0957),
+    (0x0962, 0x0963),
+    (0x0981, 0x0983),
+    (0x09BC, 0x09D7),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1160, 0x11FF), // гласные и конечные согласные чамо хангыля
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200C, 0x200D),
+    (0x20D0, 0x20FF),
+    (0x302A, 0x302F),
+    (0x3099, 0x309A),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0020, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+/// Символы нулевой ширины, которые сами по себе образуют кластер.
+const ZERO_WIDTH: &[(u32, u32)] = &[(0x200B, 0x200B), (0x200E, 0x200F), (0x2060, 0x2064), (0xFEFF, 0xFEFF)];
+
+/// Широкие (W) и полноширинные (F) символы, включая эмодзи с эмодзи-представлением.
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18AFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F202),
+    (0x1F210, 0x1F23B),
+    (0x1F240, 0x1F248),
+    (0x1F250, 0x1F251),
+    (0x1F260, 0x1F265),
+    (0x1F300, 0x1F320),
+    (0x1F32D, 0x1F335),
+    (0x1F337, 0x1F37C),
+    (0x1F37E, 0x1F393),
+    (0x1F3A0, 0x1F3CA),
+    (0x1F3CF, 0x1F3D3),
+    (0x1F3E0, 0x1F3F0),
+    (0x1F3F4, 0x1F3F4),
+    (0x1F3F8, 0x1F43E),
+    (0x1F440, 0x1F440),
+    (0x1F442, 0x1F4FC),
+    (0x1F4FF, 0x1F53D),
+    (0x1F54B, 0x1F54E),
+    (0x1F550, 0x1F567),
+    (0x1F57A, 0x1F57A),
+    (0x1F595, 0x1F596),
+    (0x1F5A4, 0x1F5A4),
+    (0x1F5FB, 0x1F64F),
+    (0x1F680, 0x1F6C5),
+    (0x1F6CC, 0x1F6CC),
+    (0x1F6D0, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7),
+    (0x1F6EB, 0x1F6EC),
+    (0x1F6F4, 0x1F6FC),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+const ZWJ: char = '\u{200D}';
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+fn in_table(c: char, table: &[(u32, u32)]) -> bool {
+    let c = c as u32;
+    table
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// Ширина одного символа вне контекста: 0, 1 или 2 столбца.
+pub fn char_width(c: char) -> usize {
+    if c.is_control() || in_table(c, EXTEND) || in_table(c, ZERO_WIDTH) {
+        0
+    } else if in_table(c, WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Длина escape-последовательности CSI (`ESC [ ... m` и подобных) в начале строки.
+/// Такие последовательности считаются отдельным кластером нулевой ширины.
+fn escape_length(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix("\x1b[")?;
+    let end = rest.find(|c: char| ('\u{40}'..='\u{7E}').contains(&c))?;
+    Some(2 + end + 1)
+}
+
+/// Разбивает строку на графемные кластеры — то, что пользователь видит как один символ.
+pub fn graphemes(text: &str) -> Graphemes<'_> {
+    Graphemes { text }
+}
+
+pub struct Graphemes<'a> {
+    text: &'a str,
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if let Some(length) = escape_length(self.text) {
+            let (escape, rest) = self.text.split_at(length);
+            self.text = rest;
+            return Some(escape);
+        }
+
+        let mut chars = self.text.char_indices();
+        let (_, first) = chars.next()?;
+        let mut end = first.len_utf8();
+        let mut previous = first;
+        let mut regional = usize::from(is_regional_indicator(first));
+
+        for (i, c) in chars {
+            let joins = (previous == '\r' && c == '\n')
+                || (!previous.is_control() && in_table(c, EXTEND))
+                || (previous == ZWJ && !c.is_control())
+                || (regional % 2 == 1 && is_regional_indicator(c));
+            if !joins {
+                break;
+            }
+            if is_regional_indicator(c) {
+                regional += 1;
+            }
+            end = i + c.len_utf8();
+            previous = c;
+        }
+
+        let (grapheme, rest) = self.text.split_at(end);
+        self.text = rest;
+        Some(grapheme)
+    }
+}
+
+/// Ширина графемного кластера: определяется его первым символом, кроме эмодзи-последовательностей.
+pub fn grapheme_width(grapheme: &str) -> usize {
+    let mut chars = grapheme.chars();
+    let Some(first) = chars.next() else {
+        return 0;
+    };
+    if is_regional_indicator(first) {
+        return 2;
+    }
+    let width = char_width(first);
+    if width == 1 && grapheme.contains(EMOJI_PRESENTATION) {
+        return 2;
+    }
+    width
+}
+
+/// Ширина строки в столбцах терминала.
+pub fn str_width(text: &str) -> usize {
+    graphemes(text).map(grapheme_width).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clusters() {
+        fn split(s: &str) -> Vec<&str> {
+            graphemes(s).collect()
+        }
+        assert_eq!(split("ab"), vec!["a", "b"]);
+        assert_eq!(split("e\u{301}x"), vec!["e\u{301}", "x"]);
+        assert_eq!(split("👍🏽!"), vec!["👍🏽", "!"]);
+        assert_eq!(split("👨\u{200D}👩\u{200D}👧"), vec!["👨\u{200D}👩\u{200D}👧"]);
+        assert_eq!(split("🇷🇺🇯🇵"), vec!["🇷🇺", "🇯🇵"]);
+        assert_eq!(split("\r\n"), vec!["\r\n"]);
+        assert_eq!(split("\x1b[1;31mя\x1b[0m"), vec!["\x1b[1;31m", "я", "\x1b[0m"]);
+    }
+
+    #[test]
+    fn widths() {
+        assert_eq!(str_width("Привет"), 6);
+        assert_eq!(str_width("日本語"), 6);
+        assert_eq!(str_width("e\u{301}"), 1);
+        assert_eq!(str_width("👍🏽"), 2);
+        assert_eq!(str_width("👨\u{200D}👩\u{200D}👧"), 2);
+        assert_eq!(str_width("🇷🇺"), 2);
+        assert_eq!(str_width("❤\u{FE0F}"), 2);
+        assert_eq!(str_width("한글"), 4);
+        assert_eq!(str_width("\x1b[38;5;208mок\x1b[0m"), 2);
+    }
+}
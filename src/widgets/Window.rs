@@ -1,47 +1,349 @@
-use std::fmt::Write;
+use crate::widgets::access::{AccessRole, Accessible};
+use crate::widgets::border::BorderStyle;
+use crate::widgets::canvas::Canvas;
+use crate::widgets::document::Value;
+use crate::widgets::layout::{fit, text_width, truncate, Align, Constraints, Rect, Size};
+use crate::widgets::menu::MenuBar;
+use crate::widgets::style::Style;
+use crate::widgets::theme::{Role, Theme, Themed};
 use crate::widgets::Widget;
 
 pub struct Window {
     title: String,
     widgets: Vec<Box<dyn Widget>>,
+    size: Option<Size>,
+    border: Themed<BorderStyle>,
+    border_style: Themed<Style>,
+    title_style: Themed<Style>,
+    /// Собственная тема окна: действует на всё поддерево вместо темы родителя.
+    theme: Option<Theme>,
+    /// Строка меню занимает место заголовка, а заголовок переезжает в верхнюю рамку.
+    menu_bar: Option<MenuBar>,
 }
 
 impl Window {
     pub fn new(title: &str) -> Window {
-        Window { title: title.to_owned(), widgets: Vec::new() }
+        Window {
+            title: title.to_owned(),
+            widgets: Vec::new(),
+            size: None,
+            border: Themed::new(BorderStyle::ASCII),
+            border_style: Themed::new(Style::default()),
+            title_style: Themed::new(Style::default()),
+            theme: None,
+            menu_bar: None,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
     }
 
     pub fn add_widget(&mut self, widget: Box<dyn Widget>) {
         self.widgets.push(widget);
     }
 
+    pub fn set_menu_bar(&mut self, menu_bar: MenuBar) {
+        self.menu_bar = Some(menu_bar);
+    }
+
+    pub fn menu_bar(&self) -> Option<&MenuBar> {
+        self.menu_bar.as_ref()
+    }
+
+    pub fn menu_bar_mut(&mut self) -> Option<&mut MenuBar> {
+        self.menu_bar.as_mut()
+    }
+
+    /// Фиксирует внешний размер окна; дочерние виджеты вписываются внутрь.
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.size = Some(Size::new(width, height));
+    }
+
+    pub fn set_border(&mut self, border: BorderStyle) {
+        self.border.set(border);
+    }
+
+    pub fn set_border_style(&mut self, style: Style) {
+        self.border_style.set(style);
+    }
+
+    /// Стиль строки заголовка между вертикальными краями рамки.
+    pub fn set_title_style(&mut self, style: Style) {
+        self.title_style.set(style);
+    }
+
+    /// Закрепляет за окном тему и сразу применяет её ко всему содержимому.
+    /// Темы, приходящие потом от родителей, это окно не меняют.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Some(theme.clone());
+        self.apply_theme(&theme);
+    }
+
+    /// Область под содержимым: без рамки, заголовка и отступа в один столбец.
+    fn content_area(area: Rect) -> Rect {
+        Rect::new(area.x + 2, area.y + 3, area.width.saturating_sub(4), area.height.saturating_sub(4))
+    }
+
+    /// Дочерние виджеты стоят столбцом; те, что не поместились по высоте, не рисуются.
+    fn layout_children(&self, area: Rect) -> Vec<Rect> {
+        let content = Window::content_area(area);
+        let mut y = content.y;
+        let mut areas = Vec::new();
+        for widget in &self.widgets {
+            let remaining = content.bottom().saturating_sub(y);
+            if remaining == 0 {
+                break;
+            }
+            let child = widget.measure(Constraints::loose(Size::new(content.width, remaining)));
+            areas.push(Rect::new(content.x, y, child.width, child.height));
+            y += child.height;
+        }
+        areas
+    }
+
+    /// Строка меню под верхней рамкой; раскрытые меню могут занять всё окно.
+    fn menu_bar_area(&self, area: Rect) -> Option<Rect> {
+        let menu_bar = self.menu_bar.as_ref()?;
+        let height = if menu_bar.is_open() { area.height.saturating_sub(2) } else { 1 };
+        Some(Rect::new(area.x + 1, area.y + 1, area.width.saturating_sub(2), height))
+    }
+
     fn inner_width(&self) -> usize {
-        std::cmp::max(
-            self.title.chars().count(),
-            self.widgets.iter().map(|w| w.width()).max().unwrap_or(0),
-        )
+        let title = match &self.menu_bar {
+            // заголовок в рамке с пробелами по бокам, строка меню без отступа
+            Some(menu_bar) => (text_width(&self.title) + 2).max(menu_bar.width().saturating_sub(2)),
+            None => text_width(&self.title),
+        };
+        std::cmp::max(title, self.widgets.iter().map(|w| w.width()).max().unwrap_or(0))
+    }
+}
+
+/// Пошаговая сборка окна: `Window::builder().title("…").child(Label::new("…")).build()`.
+pub struct WindowBuilder {
+    window: Window,
+}
+
+impl Window {
+    pub fn builder() -> WindowBuilder {
+        WindowBuilder { window: Window::new("") }
+    }
+}
+
+impl WindowBuilder {
+    pub fn title(mut self, title: &str) -> WindowBuilder {
+        self.window.title = title.to_owned();
+        self
+    }
+
+    pub fn size(mut self, size: impl Into<Size>) -> WindowBuilder {
+        let size = size.into();
+        self.window.set_size(size.width, size.height);
+        self
+    }
+
+    pub fn border(mut self, border: BorderStyle) -> WindowBuilder {
+        self.window.set_border(border);
+        self
+    }
+
+    pub fn title_style(mut self, style: Style) -> WindowBuilder {
+        self.window.set_title_style(style);
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> WindowBuilder {
+        self.window.theme = Some(theme);
+        self
+    }
+
+    pub fn menu_bar(mut self, menu_bar: MenuBar) -> WindowBuilder {
+        self.window.set_menu_bar(menu_bar);
+        self
+    }
+
+    pub fn child(mut self, widget: impl Widget + 'static) -> WindowBuilder {
+        self.window.add_widget(Box::new(widget));
+        self
+    }
+
+    /// Закреплённая тема применяется, когда окно собрано целиком.
+    pub fn build(mut self) -> Window {
+        if let Some(theme) = self.window.theme.clone() {
+            self.window.apply_theme(&theme);
+        }
+        self.window
     }
 }
 
 impl Widget for Window {
     fn width(&self) -> usize {
-        self.inner_width() + 4
+        match self.size {
+            Some(size) => size.width,
+            None => self.inner_width() + 4,
+        }
     }
 
-    fn draw_into(&self, buffer: &mut dyn Write) {
-        let mut inner = String::new();
-        for widget in &self.widgets {
-            widget.draw_into(&mut inner);
+    fn height(&self) -> usize {
+        match self.size {
+            Some(size) => size.height,
+            // заголовок, две линии рамки и разделитель
+            None => self.widgets.iter().map(|w| w.height()).sum::<usize>() + 4,
+        }
+    }
+
+    fn accessible(&self) -> Accessible {
+        Accessible::new(AccessRole::Window, &self.title)
+    }
+
+    fn render(&self, canvas: &mut Canvas, area: Rect) {
+        if area.width < 4 || area.height < 4 {
+            return;
+        }
+        let border = &self.border.get();
+        let inner_width = area.width - 4;
+        canvas.draw_box(area, border, self.border_style.get());
+
+        let title_bar = Rect::new(area.x + 1, area.y + 1, area.width - 2, 1);
+        let title_style = self.title_style.get();
+        if self.menu_bar.is_some() {
+            if !self.title.is_empty() {
+                canvas.put_str(area.x + 2, area.y, &truncate(&format!(" {} ", self.title), inner_width), title_style);
+            }
+        } else {
+            canvas.fill(title_bar, " ", title_style);
+            canvas.put_str(area.x + 2, area.y + 1, &fit(&self.title, inner_width, Align::Center), title_style);
         }
+        let header = BorderStyle::line(border.header_left, border.header, area.width - 2, border.header_right);
+        canvas.put_border(area.x, area.y + 2, &header, self.border_style.get());
 
-        let inner_width = self.inner_width();
+        for (widget, child) in self.widgets.iter().zip(self.layout_children(area)) {
+            canvas.render_widget(widget.as_ref(), child);
+        }
+        // строка меню рисуется последней, чтобы раскрытые меню легли поверх содержимого
+        if let (Some(menu_bar), Some(bar)) = (&self.menu_bar, self.menu_bar_area(area)) {
+            canvas.render_widget(menu_bar, bar);
+        }
+    }
+
+    /// Окно со строкой меню не описывается: команды меню — это замыкания.
+    fn describe(&self) -> Option<Value> {
+        if self.menu_bar.is_some() {
+            return None;
+        }
+        let mut properties = vec![("type", Value::from("window")), ("title", Value::from(self.title.as_str()))];
+        if let Some(size) = self.size {
+            properties.push(("width", Value::from(size.width)));
+            properties.push(("height", Value::from(size.height)));
+        }
+        if self.border.get() != BorderStyle::ASCII {
+            properties.push(("border", Value::from(self.border.get().name()?)));
+        }
+        let children = self.widgets.iter().map(|widget| widget.describe()).collect::<Option<Vec<_>>>()?;
+        properties.push(("children", Value::from(children)));
+        Some(Value::table(properties))
+    }
 
-        writeln!(buffer, "+-{:-<inner_width$}-+", "-").unwrap();
-        writeln!(buffer, "| {:^inner_width$} |", &self.title).unwrap();
-        writeln!(buffer, "+={:=<inner_width$}=+", "-").unwrap();
-        for line in inner.lines() {
-            writeln!(buffer, "| {:inner_width$} |", line).unwrap();
+    fn apply_theme(&mut self, theme: &Theme) {
+        let theme = self.theme.clone().unwrap_or_else(|| theme.clone());
+        self.border.theme(theme.window_border);
+        self.border_style.theme(theme.style(Role::Border));
+        self.title_style.theme(theme.style(Role::WindowTitle));
+        if let Some(menu_bar) = &mut self.menu_bar {
+            menu_bar.apply_theme(&theme);
+        }
+        for widget in &mut self.widgets {
+            widget.apply_theme(&theme);
         }
-        writeln!(buffer, "+-{:-<inner_width$}-+", "-").unwrap();
     }
-}
\ No newline at end of file
+
+    /// Строка меню идёт первой: при открытом меню щелчки по нему не должны доставаться содержимому.
+    fn children(&self) -> Vec<&dyn Widget> {
+        let menu_bar = self.menu_bar.iter().map(|menu_bar| menu_bar as &dyn Widget);
+        menu_bar.chain(self.widgets.iter().map(|widget| widget.as_ref())).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        let menu_bar = self.menu_bar.iter_mut().map(|menu_bar| menu_bar as &mut dyn Widget);
+        menu_bar.chain(self.widgets.iter_mut().map(|widget| widget.as_mut() as &mut dyn Widget)).collect()
+    }
+
+    fn child_areas(&self, area: Rect) -> Vec<Rect> {
+        self.menu_bar_area(area).into_iter().chain(self.layout_children(area)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::{Button, Label};
+    use crate::widgets::snapshot::{plain, render};
+
+    #[test]
+    fn fixed_size_window() {
+        let mut window = Window::new("Окно");
+        window.add_widget(Box::new(Label::new("очень длинная строка текста")));
+        window.add_widget(Box::new(Button::new("OK")));
+        window.set_size(12, 8);
+
+        let out = render(&window);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert!(lines.iter().all(|line| text_width(line) == 12));
+        assert_eq!(lines[3], "| очень дл |");
+        assert_eq!(lines[4], "| +------+ |");
+    }
+
+    #[test]
+    fn natural_size() {
+        let mut window = Window::new("設定");
+        window.add_widget(Box::new(Label::new("日本語のラベル")));
+        window.add_widget(Box::new(Button::new("OK")));
+        let out = render(&window);
+        assert_eq!(out.lines().count(), window.height());
+        assert!(out.lines().all(|line| text_width(line) == window.width()));
+    }
+
+    #[test]
+    fn double_border() {
+        let mut window = Window::new("T");
+        window.set_border(BorderStyle::DOUBLE);
+        window.add_widget(Box::new(Label::new("abc")));
+        let out = render(&window);
+        assert_eq!(out, "╔═════╗\n║  T  ║\n╠═════╣\n║ abc ║\n╚═════╝\n");
+    }
+
+    #[test]
+    fn menu_bar_under_border() {
+        use crate::widgets::app::App;
+        use crate::widgets::event::{Event, KeyCode, KeyEvent};
+        use crate::widgets::menu::{Menu, MenuItem};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let saved = Rc::new(Cell::new(false));
+        let mut menu_bar = MenuBar::new().menu(Menu::new("&Файл").item(MenuItem::new("&Сохранить", "save")));
+        let flag = Rc::clone(&saved);
+        menu_bar.on_command("save", move || flag.set(true));
+        let window = Window::builder().title("Блокнот").menu_bar(menu_bar).child(Button::new("OK")).build();
+        assert!(window.describe().is_none());
+
+        let mut app = App::new(Box::new(window));
+        assert_eq!(app.focused(), Some(&[1][..]));
+        app.handle_event(&Event::Key(KeyEvent::alt(KeyCode::Char('ф'))));
+        assert_eq!(app.focused(), Some(&[0][..]));
+        assert_eq!(
+            plain(&app.frame()),
+            "+- Блокнот ----+\n\
+             | Файл         |\n\
+             ++-----------+=+\n\
+             || Сохранить | |\n\
+             |+-----------+ |\n\
+             | +----------+ |\n\
+             +--------------+\n"
+        );
+        app.handle_event(&Event::key(KeyCode::Enter));
+        assert!(saved.get());
+        assert_eq!(app.focused(), Some(&[1][..]), "фокус вернулся к кнопке");
+    }
+}